@@ -0,0 +1,353 @@
+use rust_chess::logic::clock::{InvalidTimeControl, TimeControl};
+use rust_chess::logic::fen::FEN_DEFAULT;
+use rust_chess::Player;
+
+use std::error::Error;
+use std::fmt;
+
+pub const USAGE: &str = "Usage:
+  rust-chess [play] [--fen <fen>] [--side white|black] [--opponent human|random] [--time <minutes>[+<seconds>]]
+                    [--white <name>] [--black <name>] [--pgn <file>] [--load <file> [--game <number>]]
+  rust-chess perft <depth> [fen]
+  rust-chess pgn convert [--fen <fen>] [moves...]
+  rust-chess pgn replay <file>
+  rust-chess fen [fen]
+  rust-chess analyze [fen]
+  rust-chess help";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OpponentKind {
+    Human,
+    Random,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlayOptions {
+    pub fen: String,
+    pub side: Player,
+    pub opponent: OpponentKind,
+    pub time_control: Option<TimeControl>,
+    pub white: Option<String>,
+    pub black: Option<String>,
+    pub pgn_path: String,
+    pub load: Option<(String, usize)>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            fen: FEN_DEFAULT.to_string(),
+            side: Player::White,
+            opponent: OpponentKind::Human,
+            time_control: None,
+            white: None,
+            black: None,
+            pgn_path: "game.pgn".to_string(),
+            load: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+    Play(PlayOptions),
+    Perft { depth: u32, fen: String },
+    PgnConvert { fen: String, moves: Vec<String> },
+    PgnReplay(String),
+    Fen(String),
+    Analyze(String),
+    Help,
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self, CliError> {
+        let (command, rest) = match args.split_first() {
+            Some((command, rest)) => (command.as_str(), rest),
+            None => return Ok(Self::Play(PlayOptions::default())),
+        };
+
+        match command {
+            "play" => Ok(Self::Play(parse_play_options(rest)?)),
+            // Options without a subcommand start a game
+            option if option.starts_with("--") => Ok(Self::Play(parse_play_options(args)?)),
+            "perft" => {
+                let (depth, fen) = match rest.split_first() {
+                    Some((depth, fen)) => (depth, fen),
+                    None => return Err(CliError::MissingArgument("perft", "depth")),
+                };
+                let depth = depth
+                    .parse()
+                    .map_err(|_| CliError::InvalidValue("depth", depth.clone()))?;
+
+                Ok(Self::Perft {
+                    depth,
+                    fen: fen_argument(fen),
+                })
+            }
+            "pgn" => match rest.split_first() {
+                Some((subcommand, rest)) if subcommand == "convert" => parse_pgn_convert(rest),
+                Some((subcommand, [path])) if subcommand == "replay" => {
+                    Ok(Self::PgnReplay(path.clone()))
+                }
+                Some((subcommand, _)) if subcommand == "replay" => {
+                    Err(CliError::MissingArgument("pgn replay", "file"))
+                }
+                Some((subcommand, _)) => {
+                    Err(CliError::UnknownCommand(format!("pgn {}", subcommand)))
+                }
+                None => Err(CliError::MissingArgument("pgn", "subcommand")),
+            },
+            "fen" => Ok(Self::Fen(fen_argument(rest))),
+            "analyze" => Ok(Self::Analyze(fen_argument(rest))),
+            "help" | "-h" => Ok(Self::Help),
+            command => Err(CliError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+fn fen_argument(args: &[String]) -> String {
+    // FENs contain spaces, so they may be passed quoted or as separate arguments
+    match args.join(" ") {
+        fen if fen.is_empty() => FEN_DEFAULT.to_string(),
+        fen => fen,
+    }
+}
+
+fn parse_play_options(args: &[String]) -> Result<PlayOptions, CliError> {
+    let mut options = PlayOptions::default();
+    let mut args = args.iter();
+
+    while let Some(option) = args.next() {
+        let mut value = |name| args.next().ok_or(CliError::MissingValue(name));
+
+        match option.as_str() {
+            "--fen" => options.fen = value("--fen")?.clone(),
+            "--side" => {
+                options.side = match value("--side")?.as_str() {
+                    "white" => Player::White,
+                    "black" => Player::Black,
+                    side => return Err(CliError::InvalidValue("--side", side.to_string())),
+                }
+            }
+            "--opponent" => {
+                options.opponent = match value("--opponent")?.as_str() {
+                    "human" => OpponentKind::Human,
+                    "random" => OpponentKind::Random,
+                    kind => return Err(CliError::InvalidValue("--opponent", kind.to_string())),
+                }
+            }
+            "--time" => {
+                options.time_control =
+                    Some(TimeControl::parse(value("--time")?).map_err(CliError::TimeControl)?)
+            }
+            "--white" => options.white = Some(value("--white")?.clone()),
+            "--black" => options.black = Some(value("--black")?.clone()),
+            "--pgn" => options.pgn_path = value("--pgn")?.clone(),
+            "--load" => options.load = Some((value("--load")?.clone(), 1)),
+            "--game" => {
+                let number = value("--game")?;
+                let game = match number.parse() {
+                    Ok(game) if game > 0 => game,
+                    _ => return Err(CliError::InvalidValue("--game", number.clone())),
+                };
+                match &mut options.load {
+                    Some((_, load_game)) => *load_game = game,
+                    None => return Err(CliError::OptionRequires("--game", "--load")),
+                }
+            }
+            option => return Err(CliError::UnknownOption(option.to_string())),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_pgn_convert(args: &[String]) -> Result<Command, CliError> {
+    // pgn convert [--fen <fen>] [moves...]
+    let (fen, moves) = match args.split_first() {
+        Some((option, rest)) if option == "--fen" => match rest.split_first() {
+            Some((fen, moves)) => (fen.clone(), moves),
+            None => return Err(CliError::MissingValue("--fen")),
+        },
+        _ => (FEN_DEFAULT.to_string(), args),
+    };
+
+    Ok(Command::PgnConvert {
+        fen,
+        moves: moves.to_vec(),
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CliError {
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingValue(&'static str),
+    MissingArgument(&'static str, &'static str),
+    OptionRequires(&'static str, &'static str),
+    InvalidValue(&'static str, String),
+    TimeControl(InvalidTimeControl),
+}
+
+impl Error for CliError {}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => write!(f, "Unknown command \"{}\"", command),
+            Self::UnknownOption(option) => write!(f, "Unknown option \"{}\"", option),
+            Self::MissingValue(option) => write!(f, "Option {} requires a value", option),
+            Self::MissingArgument(command, argument) => {
+                write!(f, "Command {} requires a {}", command, argument)
+            }
+            Self::OptionRequires(option, other) => {
+                write!(f, "Option {} can only be used with {}", option, other)
+            }
+            Self::InvalidValue(name, value) => {
+                write!(f, "Invalid value \"{}\" for {}", value, name)
+            }
+            Self::TimeControl(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_chess::logic::clock::{InvalidTimeControl, TimeControl};
+    use rust_chess::logic::fen::FEN_DEFAULT;
+    use rust_chess::Player;
+
+    use super::{CliError, Command, OpponentKind, PlayOptions};
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        Command::parse(&args)
+    }
+
+    #[test]
+    fn test_parse_play() {
+        assert_eq!(parse(""), Ok(Command::Play(PlayOptions::default())));
+        assert_eq!(parse("play"), Ok(Command::Play(PlayOptions::default())));
+
+        let expected = PlayOptions {
+            fen: "8/8/8/8/8/8/8/8".to_string(),
+            side: Player::Black,
+            opponent: OpponentKind::Random,
+            time_control: Some(TimeControl::parse("5+3").unwrap()),
+            ..PlayOptions::default()
+        };
+        assert_eq!(
+            parse("play --side black --opponent random --time 5+3 --fen 8/8/8/8/8/8/8/8"),
+            Ok(Command::Play(expected.clone()))
+        );
+        assert_eq!(
+            parse("--side black --opponent random --time 5+3 --fen 8/8/8/8/8/8/8/8"),
+            Ok(Command::Play(expected))
+        );
+
+        assert_eq!(
+            parse("play --load games.pgn --game 3"),
+            Ok(Command::Play(PlayOptions {
+                load: Some(("games.pgn".to_string(), 3)),
+                ..PlayOptions::default()
+            }))
+        );
+        assert_eq!(
+            parse("play --game 3"),
+            Err(CliError::OptionRequires("--game", "--load"))
+        );
+        assert_eq!(
+            parse("play --load games.pgn --game 0"),
+            Err(CliError::InvalidValue("--game", "0".to_string()))
+        );
+        assert_eq!(
+            parse("play --side red"),
+            Err(CliError::InvalidValue("--side", "red".to_string()))
+        );
+        assert_eq!(
+            parse("play --opponent"),
+            Err(CliError::MissingValue("--opponent"))
+        );
+        assert_eq!(
+            parse("play --time 0"),
+            Err(CliError::TimeControl(InvalidTimeControl("0".to_string())))
+        );
+        assert_eq!(
+            parse("play --depth 3"),
+            Err(CliError::UnknownOption("--depth".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            parse("perft 3"),
+            Ok(Command::Perft {
+                depth: 3,
+                fen: FEN_DEFAULT.to_string()
+            })
+        );
+        assert_eq!(
+            parse("perft 2 8/8/8/8/8/8/8/K6k w - - 0 1"),
+            Ok(Command::Perft {
+                depth: 2,
+                fen: "8/8/8/8/8/8/8/K6k w - - 0 1".to_string()
+            })
+        );
+        assert_eq!(
+            parse("perft"),
+            Err(CliError::MissingArgument("perft", "depth"))
+        );
+        assert_eq!(
+            parse("perft x"),
+            Err(CliError::InvalidValue("depth", "x".to_string()))
+        );
+
+        assert_eq!(
+            parse("pgn convert e4 e5 Nf3"),
+            Ok(Command::PgnConvert {
+                fen: FEN_DEFAULT.to_string(),
+                moves: vec!["e4".to_string(), "e5".to_string(), "Nf3".to_string()]
+            })
+        );
+        assert_eq!(
+            Command::parse(
+                &[
+                    "pgn",
+                    "convert",
+                    "--fen",
+                    "8/8/8/8/8/8/8/K6k b - - 0 1",
+                    "Kg1"
+                ]
+                .map(String::from)
+            ),
+            Ok(Command::PgnConvert {
+                fen: "8/8/8/8/8/8/8/K6k b - - 0 1".to_string(),
+                moves: vec!["Kg1".to_string()]
+            })
+        );
+        assert_eq!(
+            parse("pgn replay games.pgn"),
+            Ok(Command::PgnReplay("games.pgn".to_string()))
+        );
+        assert_eq!(
+            parse("pgn replay"),
+            Err(CliError::MissingArgument("pgn replay", "file"))
+        );
+        assert_eq!(
+            parse("pgn export"),
+            Err(CliError::UnknownCommand("pgn export".to_string()))
+        );
+
+        assert_eq!(parse("fen"), Ok(Command::Fen(FEN_DEFAULT.to_string())));
+        assert_eq!(
+            parse("analyze 8/8/8/8/8/8/8/K6k b - - 0 1"),
+            Ok(Command::Analyze("8/8/8/8/8/8/8/K6k b - - 0 1".to_string()))
+        );
+        assert_eq!(parse("help"), Ok(Command::Help));
+        assert_eq!(
+            parse("replay"),
+            Err(CliError::UnknownCommand("replay".to_string()))
+        );
+    }
+}
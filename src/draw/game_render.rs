@@ -1,31 +1,49 @@
 use crate::draw::prompt::Prompt;
-use crate::draw::terminal::Terminal;
+use crate::draw::terminal::{Terminal, TerminalError};
 use crate::draw::text::OUTPUT_ENTER_MOVE;
 use crate::logic::basic::{column_to_name, row_to_name, Coordinate, FieldColor, Player};
 use crate::logic::board::{TileContent, BOARD_MAX_AXIS, BOARD_SIZE};
+use crate::logic::clock::format_duration;
 use crate::logic::game::{Game, GameState};
 use crate::logic::intent::{Intent, PartialCoordinate};
-use crate::logic::pieces::{MoveError, PieceType};
+use crate::logic::opponent::Opponent;
+use crate::logic::pieces::PieceType;
+use crate::logic::result::Termination;
+use crate::logic::san::SanError;
 use crate::{
-    FORMAT_OUTPUT_CHECKMATE, FORMAT_OUTPUT_CIRITCAL_ERROR, FORMAT_OUTPUT_ERROR_MOVE_FROM,
-    FORMAT_OUTPUT_ERROR_MOVE_FULL, FORMAT_OUTPUT_TURN, FORMAT_OUTPUT_TURN_SHORT,
-    FORMAT_PROMPT_MOVE,
+    FORMAT_OUTPUT_CHECKMATE, FORMAT_OUTPUT_CLOCK, FORMAT_OUTPUT_DRAW_OFFER,
+    FORMAT_OUTPUT_ERROR_MOVE_FROM, FORMAT_OUTPUT_ERROR_MOVE_FULL, FORMAT_OUTPUT_PGN_ERROR,
+    FORMAT_OUTPUT_PGN_SAVED, FORMAT_OUTPUT_RESIGNATION, FORMAT_OUTPUT_RESULT,
+    FORMAT_OUTPUT_TIMEOUT, FORMAT_OUTPUT_TURN, FORMAT_OUTPUT_TURN_SHORT, FORMAT_PROMPT_MOVE,
 };
 
+use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 use termion::color;
 use termion::event::Key;
 
 use super::text::{
-    OUTPUT_HINT_PROMOTE, OUTPUT_ILLEGAL_MOVE, OUTPUT_INVALID_COMMAND, OUTPUT_MOVE_ERROR_CHECK,
-    OUTPUT_STATE_CHECK,
+    OUTPUT_CANNOT_OFFER_DRAW, OUTPUT_CANNOT_RESIGN, OUTPUT_DRAW_DECLINED, OUTPUT_FIFTY_MOVE_RULE,
+    OUTPUT_FIVEFOLD_REPETITION, OUTPUT_HINT_CLAIM_FIFTY_MOVE_RULE, OUTPUT_HINT_CLAIM_REPETITION,
+    OUTPUT_HINT_GAME_OVER, OUTPUT_HINT_PROMOTE, OUTPUT_INSUFFICIENT_MATERIAL,
+    OUTPUT_INVALID_COMMAND, OUTPUT_NO_DRAW_CLAIM, OUTPUT_NO_DRAW_OFFER, OUTPUT_OWN_DRAW_OFFER,
+    OUTPUT_SEVENTY_FIVE_MOVE_RULE, OUTPUT_STALEMATE, OUTPUT_STATE_CHECK,
+    OUTPUT_THREEFOLD_REPETITION, OUTPUT_TIMEOUT_DRAW,
 };
 
+// How long to wait for a key before checking the clock again
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+const CLOCK_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Copy, Clone)]
 enum BoardHighlight {
     None,
     Primary,
     Secondary,
+    Threat,
     Error,
 }
 
@@ -34,6 +52,7 @@ impl BoardHighlight {
         match self {
             Self::Primary => color::Bg(color::Green).to_string(),
             Self::Secondary => color::Bg(color::Blue).to_string(),
+            Self::Threat => color::Bg(color::Yellow).to_string(),
             Self::Error => color::Bg(color::Red).to_string(),
             Self::None => "".to_string(),
         }
@@ -43,6 +62,7 @@ impl BoardHighlight {
         match self {
             Self::Primary => color::Fg(color::Green).to_string(),
             Self::Secondary => color::Fg(color::Blue).to_string(),
+            Self::Threat => color::Fg(color::Yellow).to_string(),
             Self::Error => color::Fg(color::Red).to_string(),
             Self::None => "".to_string(),
         }
@@ -51,6 +71,9 @@ impl BoardHighlight {
 
 pub struct GameRenderer<'a> {
     game: &'a mut Game,
+    opponent: Opponent,
+    side: Player,
+    pgn_path: PathBuf,
     terminal: Terminal,
     prompt: Prompt,
     field_size: usize,
@@ -60,40 +83,108 @@ pub struct GameRenderer<'a> {
 }
 
 impl<'a> GameRenderer<'a> {
-    pub fn new(game: &'a mut Game) -> Self {
-        Self {
+    pub fn new(
+        game: &'a mut Game,
+        opponent: Opponent,
+        side: Player,
+        pgn_path: PathBuf,
+    ) -> Result<Self, TerminalError> {
+        Ok(Self {
             game,
-            terminal: Terminal::default(),
+            opponent,
+            side,
+            pgn_path,
+            terminal: Terminal::new()?,
             prompt: Prompt::default(),
             field_size: 4,
             horizontal_scale: 2,
             highlighted_cells: [[BoardHighlight::None; BOARD_SIZE]; BOARD_SIZE],
             output_text: "".to_string(),
-        }
+        })
     }
 
-    pub fn run(&mut self) {
+    pub fn run(&mut self) -> Result<(), TerminalError> {
+        let mut needs_redraw = true;
+        let mut last_draw = Instant::now();
+
         loop {
-            self.draw_board();
+            needs_redraw |= self.game.check_time();
+            needs_redraw |= self.play_opponent_move();
+
+            // A running clock has to be redrawn even without any input
+            let clock_ticking = self.game.clock.is_some() && !self.game.is_over();
+            if needs_redraw || (clock_ticking && last_draw.elapsed() >= CLOCK_REFRESH_INTERVAL) {
+                self.draw_board()?;
+                needs_redraw = false;
+                last_draw = Instant::now();
+            }
 
-            if let Some(key) = self.terminal.read_key() {
-                match key {
-                    Key::Char('\n') => self.on_prompt_enter(),
-                    k => self.prompt.consume_key(&k),
+            match self.terminal.read_key()? {
+                Some(Key::Ctrl('c')) => return Ok(()),
+                Some(Key::Char('\n')) => self.on_prompt_enter(),
+                Some(k) => self.prompt.consume_key(&k),
+                None => {
+                    thread::sleep(INPUT_POLL_INTERVAL);
+                    continue;
                 }
             }
+
+            needs_redraw = true;
+        }
+    }
+
+    fn play_opponent_move(&mut self) -> bool {
+        // The opponent only moves for the side the player does not control
+        if self.game.board.turn == self.side || !matches!(self.game.state, GameState::WaitMove(_)) {
+            return false;
+        }
+
+        match self.opponent.choose_move(&self.game.board) {
+            Some(a_move) => self.game.play_move(&a_move).is_ok(),
+            None => false,
         }
     }
 
     pub fn evaluate_intent(&mut self, intent: &Intent) {
-        if let Intent::Move(Some(from), maybe_to) = intent {
-            self.highlight_move(from, maybe_to);
+        if let GameState::WaitMove(true) = self.game.state {
+            let checkers = self.game.board.checkers();
+            self.highlight_threats(&checkers);
+        }
+
+        match intent {
+            Intent::Move(Some(from), maybe_to) => self.highlight_move(from, maybe_to),
+            Intent::Notation(text) => self.highlight_notation(text),
+            _ => (),
+        }
+    }
+
+    fn highlight_notation(&mut self, text: &str) {
+        // Only moves that resolve to a single legal move are shown
+        if let Ok(a_move) = self.game.board.parse_san(text) {
+            self.highlighted_cells[a_move.from.yv()][a_move.from.xv()] = BoardHighlight::Primary;
+            self.highlighted_cells[a_move.to.yv()][a_move.to.xv()] = BoardHighlight::Secondary;
+            self.set_output_text(OUTPUT_ENTER_MOVE.to_string());
+        }
+    }
+
+    fn highlight_threats(&mut self, attackers: &[Coordinate]) {
+        for coord in attackers {
+            self.highlighted_cells[coord.yv()][coord.xv()] = BoardHighlight::Threat;
         }
     }
 
     fn highlight_move(&mut self, from: &PartialCoordinate, maybe_to: &Option<PartialCoordinate>) {
         if let Some(coord_from) = from.to_complete() {
             if self.game.can_move_from(&coord_from) {
+                // Enemy pieces that could take the selected piece
+                let threats: Vec<Coordinate> = self
+                    .game
+                    .board
+                    .attackers_of(&coord_from)
+                    .into_iter()
+                    .filter(|coord| !self.game.can_move_from(coord))
+                    .collect();
+                self.highlight_threats(&threats);
                 self.highlighted_cells[coord_from.yv()][coord_from.xv()] = BoardHighlight::Primary;
 
                 if let Some(to) = maybe_to {
@@ -127,12 +218,19 @@ impl<'a> GameRenderer<'a> {
         }
     }
 
-    fn draw_prompt(&mut self, offset_x: usize, offset_y: usize, line: &String, intent: &Intent) {
+    fn draw_prompt(
+        &mut self,
+        offset_x: usize,
+        offset_y: usize,
+        line: &String,
+        intent: &Intent,
+    ) -> Result<(), TerminalError> {
         let formatted_line = self.format_prompt(line, intent);
         let turn = self.game.board.turn.to_label();
 
-        self.terminal.move_cursor(offset_x, offset_y);
-        write!(self.terminal.screen, "{}> {}", turn, formatted_line).unwrap();
+        self.terminal.move_cursor(offset_x, offset_y)?;
+        write!(self.terminal.screen, "{}> {}", turn, formatted_line)?;
+        Ok(())
     }
 
     fn format_prompt(&mut self, line: &String, intent: &Intent) -> String {
@@ -218,21 +316,101 @@ impl<'a> GameRenderer<'a> {
     pub fn execute_intent(&mut self, intent: &Intent) -> Result<(), String> {
         match intent {
             Intent::Move(Some(a), Some(b)) => self.execute_move(a, b),
+            Intent::Notation(text) => self.execute_notation(text),
             Intent::SelectPromotionType(piece_type) => self.execute_promotion(piece_type),
-            Intent::Surrender => Ok(self.execute_surrender()),
+            Intent::Surrender => self.execute_surrender(),
+            Intent::ClaimDraw => self.execute_claim_draw(),
+            Intent::OfferDraw => self.execute_offer_draw(),
+            Intent::AcceptDraw => self.execute_accept_draw(),
+            Intent::DeclineDraw => self.execute_decline_draw(),
+            Intent::NewGame => {
+                self.execute_new_game();
+                Ok(())
+            }
+            Intent::Export => {
+                self.execute_export();
+                Ok(())
+            }
+            Intent::SavePgn => self.execute_save_pgn(),
             _ => Err(OUTPUT_INVALID_COMMAND.to_string()),
         }
     }
 
-    fn execute_surrender(&mut self) {
-        let turn = &self.game.board.turn;
-        let output_text = format!(
-            "{} surrendered. {} wins!",
-            turn.to_label(),
-            turn.other().to_label()
-        );
-        self.set_output_text(output_text);
+    fn execute_surrender(&mut self) -> Result<(), String> {
+        let turn = self.game.board.turn.clone();
+        if self.game.resign(&turn) {
+            self.set_output_text("".to_string());
+            Ok(())
+        } else {
+            Err(OUTPUT_CANNOT_RESIGN.to_string())
+        }
+    }
+
+    fn execute_new_game(&mut self) {
         self.game.reset();
+        self.set_output_text("".to_string());
+    }
+
+    fn execute_export(&mut self) {
+        self.set_output_text(self.game.board.to_fen());
+    }
+
+    fn execute_save_pgn(&mut self) -> Result<(), String> {
+        let path = self.pgn_path.display().to_string();
+
+        match append_pgn(&self.pgn_path, &self.game.to_pgn()) {
+            Ok(_) => {
+                self.set_output_text(FORMAT_OUTPUT_PGN_SAVED!(path));
+                Ok(())
+            }
+            Err(err) => Err(FORMAT_OUTPUT_PGN_ERROR!(path, err)),
+        }
+    }
+
+    fn execute_claim_draw(&mut self) -> Result<(), String> {
+        if self.game.claim_draw() {
+            self.set_output_text("".to_string());
+            Ok(())
+        } else {
+            Err(OUTPUT_NO_DRAW_CLAIM.to_string())
+        }
+    }
+
+    fn execute_offer_draw(&mut self) -> Result<(), String> {
+        if self.game.offer_draw() {
+            self.set_output_text("".to_string());
+            Ok(())
+        } else {
+            Err(OUTPUT_CANNOT_OFFER_DRAW.to_string())
+        }
+    }
+
+    fn execute_accept_draw(&mut self) -> Result<(), String> {
+        let player = self.game.board.turn.clone();
+        if self.game.draw_offer.as_ref() == Some(&player) {
+            return Err(OUTPUT_OWN_DRAW_OFFER.to_string());
+        }
+
+        if self.game.accept_draw(&player) {
+            self.set_output_text("".to_string());
+            Ok(())
+        } else {
+            Err(OUTPUT_NO_DRAW_OFFER.to_string())
+        }
+    }
+
+    fn execute_decline_draw(&mut self) -> Result<(), String> {
+        let player = self.game.board.turn.clone();
+        if self.game.draw_offer.as_ref() == Some(&player) {
+            return Err(OUTPUT_OWN_DRAW_OFFER.to_string());
+        }
+
+        if self.game.decline_draw(&player) {
+            self.set_output_text(OUTPUT_DRAW_DECLINED.to_string());
+            Ok(())
+        } else {
+            Err(OUTPUT_NO_DRAW_OFFER.to_string())
+        }
     }
 
     fn execute_promotion(&mut self, piece_type: &PieceType) -> Result<(), String> {
@@ -245,7 +423,7 @@ impl<'a> GameRenderer<'a> {
                         self.set_output_text("".to_string());
                         Ok(())
                     }
-                    Err(err) => Err(FORMAT_OUTPUT_CIRITCAL_ERROR!(err)),
+                    Err(err) => Err(err.to_string()),
                 }
             }
             _ => Err("Not in promotion state".to_string()),
@@ -259,15 +437,31 @@ impl<'a> GameRenderer<'a> {
                     self.set_output_text("".to_string());
                     Ok(())
                 }
-                Err(MoveError::IsCheck) => Err(OUTPUT_MOVE_ERROR_CHECK.to_string()),
-                Err(_) => Err(OUTPUT_ILLEGAL_MOVE.to_string()),
+                Err(err) => Err(err.to_string()),
             }
         } else {
             Err(OUTPUT_INVALID_COMMAND.to_string())
         }
     }
 
-    fn draw_board(&mut self) {
+    fn execute_notation(&mut self, text: &str) -> Result<(), String> {
+        let result = match self.game.board.parse_san(text) {
+            Ok(a_move) => self.game.play_move(&a_move),
+            // Lets the player pick the piece like after a coordinate move
+            Err(SanError::MissingPromotion(from, to)) => self.game.move_piece(&from, &to),
+            Err(err) => return Err(err.to_string()),
+        };
+
+        match result {
+            Ok(_) => {
+                self.set_output_text("".to_string());
+                Ok(())
+            }
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn draw_board(&mut self) -> Result<(), TerminalError> {
         // TODO: get terminal size and only draw if size is sufficient
 
         let line = self.prompt.get_line();
@@ -276,43 +470,109 @@ impl<'a> GameRenderer<'a> {
         self.clear_highlight();
         self.evaluate_intent(&intent);
 
-        self.terminal.clear_screen();
-        self.draw_coordinates(0, 0);
-        self.draw_grid(1 * self.horizontal_scale, 1);
-        self.draw_pieces(1 * self.horizontal_scale, 1);
-        self.draw_output(0, BOARD_SIZE * self.field_size + 5);
-        self.draw_prompt(0, BOARD_SIZE * self.field_size + 4, &line, &intent);
-        self.terminal.flush();
+        self.terminal.clear_screen()?;
+        self.draw_coordinates(0, 0)?;
+        self.draw_grid(self.horizontal_scale, 1)?;
+        self.draw_pieces(self.horizontal_scale, 1)?;
+        self.draw_clock(0, BOARD_SIZE * self.field_size + 3)?;
+        self.draw_output(0, BOARD_SIZE * self.field_size + 5)?;
+        self.draw_prompt(0, BOARD_SIZE * self.field_size + 4, &line, &intent)?;
+        self.terminal.flush()
+    }
+
+    fn draw_clock(&mut self, offset_x: usize, offset_y: usize) -> Result<(), TerminalError> {
+        let clock = match &self.game.clock {
+            Some(clock) => clock,
+            None => return Ok(()),
+        };
+
+        let now = Instant::now();
+        let clock_text = FORMAT_OUTPUT_CLOCK!(
+            Player::White.to_label(),
+            format_duration(clock.remaining(&Player::White, now)),
+            Player::Black.to_label(),
+            format_duration(clock.remaining(&Player::Black, now))
+        );
+
+        self.terminal.move_cursor(offset_x, offset_y)?;
+        write!(self.terminal.screen, "{}", clock_text)?;
+        Ok(())
     }
 
     fn set_output_text(&mut self, new_text: String) {
         self.output_text = new_text;
     }
 
-    fn draw_output(&mut self, offset_x: usize, offset_y: usize) {
-        self.terminal.move_cursor(offset_x, offset_y);
+    fn draw_output(&mut self, offset_x: usize, offset_y: usize) -> Result<(), TerminalError> {
+        self.terminal.move_cursor(offset_x, offset_y)?;
 
         let output_text = if self.output_text.is_empty() {
             match self.game.state {
                 GameState::SelectPromotionType(..) => OUTPUT_HINT_PROMOTE.to_string(),
-                GameState::WaitMove(true) => format!(
-                    "{}{}{} {}",
-                    color::Fg(color::Yellow),
-                    OUTPUT_STATE_CHECK,
-                    color::Fg(color::Reset),
+                // A pending draw offer must not hide that the player to move is in check
+                GameState::WaitMove(is_check) if self.game.draw_offer.is_some() => {
+                    let offering = self.game.draw_offer.as_ref().unwrap();
+                    let offer =
+                        FORMAT_OUTPUT_DRAW_OFFER!(offering.to_label(), offering.other().to_label());
+                    match is_check {
+                        true => with_check_notice(&offer),
+                        false => offer,
+                    }
+                }
+                GameState::WaitMove(true) => {
+                    with_check_notice(&FORMAT_OUTPUT_TURN_SHORT!(self.game.board.turn.to_label()))
+                }
+                GameState::GameOver => {
+                    format!("{} {}", self.format_result(), OUTPUT_HINT_GAME_OVER)
+                }
+                _ if self.game.can_claim_draw() => format!(
+                    "{} {}",
                     FORMAT_OUTPUT_TURN_SHORT!(self.game.board.turn.to_label()),
+                    match self.game.claimable_draw() {
+                        Some(Termination::FiftyMoveRule) => OUTPUT_HINT_CLAIM_FIFTY_MOVE_RULE,
+                        _ => OUTPUT_HINT_CLAIM_REPETITION,
+                    },
                 ),
-                GameState::CheckMate => FORMAT_OUTPUT_CHECKMATE!(self.game.board.turn.to_label()),
                 _ => FORMAT_OUTPUT_TURN!(self.game.board.turn.to_label()),
             }
         } else {
             self.output_text.clone()
         };
 
-        write!(self.terminal.screen, "{}", output_text).unwrap();
+        write!(self.terminal.screen, "{}", output_text)?;
+        Ok(())
     }
 
-    fn draw_coordinates(&mut self, offset_x: usize, offset_y: usize) {
+    fn format_result(&self) -> String {
+        let result = match &self.game.result {
+            Some(result) => result,
+            None => return "".to_string(),
+        };
+
+        match (&result.termination, result.winner()) {
+            (Termination::Checkmate, Some(winner)) => {
+                FORMAT_OUTPUT_CHECKMATE!(winner.other().to_label())
+            }
+            (Termination::Resignation, Some(winner)) => {
+                FORMAT_OUTPUT_RESIGNATION!(winner.other().to_label(), winner.to_label())
+            }
+            (Termination::Timeout, Some(winner)) => {
+                FORMAT_OUTPUT_TIMEOUT!(winner.other().to_label(), winner.to_label())
+            }
+            (Termination::Timeout, None) => OUTPUT_TIMEOUT_DRAW.to_string(),
+            (Termination::Stalemate, None) => OUTPUT_STALEMATE.to_string(),
+            (Termination::InsufficientMaterial, None) => OUTPUT_INSUFFICIENT_MATERIAL.to_string(),
+            (Termination::ThreefoldRepetition, None) => OUTPUT_THREEFOLD_REPETITION.to_string(),
+            (Termination::FivefoldRepetition, None) => OUTPUT_FIVEFOLD_REPETITION.to_string(),
+            (Termination::FiftyMoveRule, None) => OUTPUT_FIFTY_MOVE_RULE.to_string(),
+            (Termination::SeventyFiveMoveRule, None) => OUTPUT_SEVENTY_FIVE_MOVE_RULE.to_string(),
+            (termination, _) => {
+                FORMAT_OUTPUT_RESULT!(result.outcome.to_token(), termination.to_label())
+            }
+        }
+    }
+
+    fn draw_coordinates(&mut self, offset_x: usize, offset_y: usize) -> Result<(), TerminalError> {
         let v_center = self.field_size / 2;
         let h_center = v_center * self.horizontal_scale;
 
@@ -325,8 +585,8 @@ impl<'a> GameRenderer<'a> {
                 let coord = Coordinate::try_new(x, y * BOARD_MAX_AXIS).unwrap();
                 let label = column_to_name(coord.xv());
 
-                self.terminal.move_cursor(pos_x, pos_y);
-                write!(self.terminal.screen, "{}", label).unwrap();
+                self.terminal.move_cursor(pos_x, pos_y)?;
+                write!(self.terminal.screen, "{}", label)?;
             }
         }
 
@@ -339,13 +599,15 @@ impl<'a> GameRenderer<'a> {
                 let coord = Coordinate::try_new(x * BOARD_MAX_AXIS, y).unwrap();
                 let label = row_to_name(coord.yv());
 
-                self.terminal.move_cursor(pos_x, pos_y);
-                write!(self.terminal.screen, "{}", label).unwrap();
+                self.terminal.move_cursor(pos_x, pos_y)?;
+                write!(self.terminal.screen, "{}", label)?;
             }
         }
+
+        Ok(())
     }
 
-    fn draw_grid(&mut self, offset_x: usize, offset_y: usize) {
+    fn draw_grid(&mut self, offset_x: usize, offset_y: usize) -> Result<(), TerminalError> {
         let h_bar = '-';
         let v_bar = '|';
         let cross = '+';
@@ -356,15 +618,15 @@ impl<'a> GameRenderer<'a> {
             for x in 0..=BOARD_SIZE {
                 let pos_x = x * self.field_size * self.horizontal_scale + offset_x;
 
-                self.terminal.move_cursor(pos_x, pos_y);
+                self.terminal.move_cursor(pos_x, pos_y)?;
 
                 // Top left corss
-                write!(self.terminal.screen, "{}", cross).unwrap();
+                write!(self.terminal.screen, "{}", cross)?;
 
                 // Horizontal
                 if x < BOARD_SIZE {
                     for _ in 1..(self.field_size * self.horizontal_scale) {
-                        write!(self.terminal.screen, "{}", h_bar).unwrap();
+                        write!(self.terminal.screen, "{}", h_bar)?;
                     }
                 }
 
@@ -372,8 +634,8 @@ impl<'a> GameRenderer<'a> {
                 if y < BOARD_SIZE {
                     for w in 1..self.field_size {
                         // TODO: Improve this so the cursor does not have to be moved for every line
-                        self.terminal.move_cursor(pos_x, pos_y + w);
-                        write!(self.terminal.screen, "{}", v_bar).unwrap();
+                        self.terminal.move_cursor(pos_x, pos_y + w)?;
+                        write!(self.terminal.screen, "{}", v_bar)?;
                     }
                 }
 
@@ -381,12 +643,12 @@ impl<'a> GameRenderer<'a> {
                 if x < BOARD_SIZE && y < BOARD_SIZE {
                     let background_color =
                         self.get_background_color_at(&Coordinate::try_new(x, y).unwrap());
-                    let board_highlight = &self.highlighted_cells[y as usize][x as usize];
+                    let board_highlight = &self.highlighted_cells[y][x];
                     let is_highlighted = !matches!(board_highlight, BoardHighlight::None);
 
                     for yi in 0..self.field_size {
                         for xi in 0..self.field_size * self.horizontal_scale {
-                            self.terminal.move_cursor(pos_x + xi + 1, pos_y + yi + 1);
+                            self.terminal.move_cursor(pos_x + xi + 1, pos_y + yi + 1)?;
 
                             if is_highlighted && (xi + yi) % 2 == 0 {
                                 write!(
@@ -394,25 +656,25 @@ impl<'a> GameRenderer<'a> {
                                     "{}*{}",
                                     board_highlight.background_color(),
                                     color::Bg(color::Reset),
-                                )
-                                .unwrap();
+                                )?;
                             } else {
                                 write!(
                                     self.terminal.screen,
                                     "{} {}",
                                     background_color,
                                     color::Bg(color::Reset),
-                                )
-                                .unwrap();
+                                )?;
                             };
                         }
                     }
                 }
             }
         }
+
+        Ok(())
     }
 
-    fn draw_pieces(&mut self, offset_x: usize, offset_y: usize) {
+    fn draw_pieces(&mut self, offset_x: usize, offset_y: usize) -> Result<(), TerminalError> {
         let v_center = self.field_size / 2;
         let h_center = v_center * self.horizontal_scale;
 
@@ -435,7 +697,7 @@ impl<'a> GameRenderer<'a> {
                     let background_color = self.get_background_color_at(&coordinate);
 
                     self.terminal
-                        .move_cursor(pos_x + h_center, pos_y + v_center);
+                        .move_cursor(pos_x + h_center, pos_y + v_center)?;
                     write!(
                         self.terminal.screen,
                         "{}{}{}{}",
@@ -443,11 +705,12 @@ impl<'a> GameRenderer<'a> {
                         label,
                         color::Bg(color::Reset),
                         color::Fg(color::Reset),
-                    )
-                    .unwrap();
+                    )?;
                 }
             }
         }
+
+        Ok(())
     }
 
     fn get_background_color_at(&self, coordinate: &Coordinate) -> String {
@@ -457,3 +720,23 @@ impl<'a> GameRenderer<'a> {
         }
     }
 }
+
+fn with_check_notice(text: &str) -> String {
+    format!(
+        "{}{}{} {}",
+        color::Fg(color::Yellow),
+        OUTPUT_STATE_CHECK,
+        color::Fg(color::Reset),
+        text
+    )
+}
+
+fn append_pgn(path: &Path, pgn: &str) -> std::io::Result<()> {
+    // PGN files hold several games, so earlier games are kept and separated by a blank line
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if file.metadata()?.len() > 0 {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(pgn.as_bytes())
+}
@@ -7,14 +7,16 @@ pub struct Prompt {
     position: usize,
 }
 
-impl Prompt {
-    pub fn default() -> Self {
+impl Default for Prompt {
+    fn default() -> Self {
         Self {
             line: ['\0'; MAX_LINE_LENGTH],
             position: 0,
         }
     }
+}
 
+impl Prompt {
     pub fn consume_key(&mut self, key: &Key) {
         match key {
             Key::Char(c) => self.add_char(c),
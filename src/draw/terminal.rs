@@ -1,61 +1,84 @@
+use std::error::Error;
+use std::fmt;
 use std::io::{self, Write};
 use termion::event::Key;
-use termion::input::TermRead;
+use termion::input::{Keys, TermRead};
 use termion::raw::{IntoRawMode, RawTerminal};
 use termion::screen::AlternateScreen;
+use termion::AsyncReader;
 
 pub struct Terminal {
     pub screen: AlternateScreen<io::Stdout>,
     _stdout: RawTerminal<std::io::Stdout>,
+    keys: Keys<AsyncReader>,
 }
 
 impl Terminal {
-    pub fn default() -> Self {
-        Self {
+    pub fn new() -> Result<Self, TerminalError> {
+        Ok(Self {
             screen: AlternateScreen::from(io::stdout()),
-            _stdout: io::stdout().into_raw_mode().unwrap(),
-        }
+            _stdout: io::stdout()
+                .into_raw_mode()
+                .map_err(TerminalError::RawMode)?,
+            keys: termion::async_stdin().keys(),
+        })
     }
 
-    pub fn read_key_raw(&self) -> Option<Key> {
-        let result = io::stdin().lock().keys().next();
-
-        match result {
-            Some(Ok(key)) => Some(key),
-            _ => None,
-        }
+    pub fn read_key(&mut self) -> Result<Option<Key>, TerminalError> {
+        // Does not block, None means no key has been pressed yet
+        self.keys.next().transpose().map_err(TerminalError::Input)
     }
 
-    pub fn read_key(&self) -> Option<Key> {
-        let key = self.read_key_raw();
-
-        match key {
-            Some(Key::Ctrl('c')) => panic!("Program end"),
-            _ => key,
-        }
+    pub fn clear_screen(&mut self) -> Result<(), TerminalError> {
+        write!(self.screen, "{}", termion::clear::All)?;
+        Ok(())
     }
 
-    pub fn clear_screen(&mut self) {
-        write!(self.screen, "{}", termion::clear::All).unwrap();
+    pub fn flush(&mut self) -> Result<(), TerminalError> {
+        self.screen.flush()?;
+        Ok(())
     }
 
-    pub fn flush(&mut self) {
-        self.screen.flush().unwrap();
-    }
-
-    pub fn move_cursor(&mut self, x: usize, y: usize) {
+    pub fn move_cursor(&mut self, x: usize, y: usize) -> Result<(), TerminalError> {
+        // Positions beyond the terminal size end up at its border
         write!(
             self.screen,
             "{}",
             termion::cursor::Goto(
-                x.saturating_add(1).try_into().unwrap(),
-                y.saturating_add(1).try_into().unwrap(),
+                x.saturating_add(1).try_into().unwrap_or(u16::MAX),
+                y.saturating_add(1).try_into().unwrap_or(u16::MAX),
             ),
-        )
-        .unwrap();
+        )?;
+        Ok(())
+    }
+
+    pub fn hide_cursor(&mut self) -> Result<(), TerminalError> {
+        write!(self.screen, "{}", termion::cursor::Hide)?;
+        Ok(())
     }
+}
+
+#[derive(Debug)]
+pub enum TerminalError {
+    RawMode(io::Error),
+    Input(io::Error),
+    Output(io::Error),
+}
+
+impl Error for TerminalError {}
+
+impl fmt::Display for TerminalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RawMode(err) => write!(f, "Could not switch the terminal to raw mode: {}", err),
+            Self::Input(err) => write!(f, "Could not read from the terminal: {}", err),
+            Self::Output(err) => write!(f, "Could not write to the terminal: {}", err),
+        }
+    }
+}
 
-    pub fn hide_cursor(&mut self) {
-        write!(self.screen, "{}", termion::cursor::Hide).unwrap();
+impl From<io::Error> for TerminalError {
+    fn from(err: io::Error) -> Self {
+        Self::Output(err)
     }
 }
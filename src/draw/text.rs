@@ -8,7 +8,7 @@ macro_rules! FORMAT_OUTPUT_TURN_SHORT {
 macro_rules! FORMAT_OUTPUT_TURN {
     ($a:expr) => {
         format!(
-            "It is {}'s turn.  Enter D2D3 to move from D2 to D3, surrender to give up, ^C to exit.",
+            "It is {}'s turn.  Enter D2D3 or a move like Nf3 or exd5, draw to offer a draw, surrender to give up, pgn to save the game, ^C to exit.",
             $a
         )
     };
@@ -33,21 +33,60 @@ macro_rules! FORMAT_OUTPUT_CHECKMATE {
     };
 }
 #[macro_export]
-macro_rules! FORMAT_OUTPUT_ERROR {
+macro_rules! FORMAT_OUTPUT_RESIGNATION {
+    ($a:expr,$b:expr) => {
+        format!("{} surrendered. {} wins!", $a, $b)
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_OUTPUT_TIMEOUT {
+    ($a:expr,$b:expr) => {
+        format!("{} ran out of time. {} wins!", $a, $b)
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_OUTPUT_CLOCK {
+    ($a:expr,$b:expr,$c:expr,$d:expr) => {
+        format!("{} {}  {} {}", $a, $b, $c, $d)
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_OUTPUT_PGN_SAVED {
     ($a:expr) => {
-        format!("Checkmate! Sorry, you lost, {}!", $a)
+        format!("Added the game to {}.", $a)
     };
 }
 #[macro_export]
-macro_rules! FORMAT_PROMPT_MOVE {
+macro_rules! FORMAT_OUTPUT_PGN_ERROR {
     ($a:expr,$b:expr) => {
-        format!("{} to {}", $a, $b)
+        format!("Could not save the game to {}: {}", $a, $b)
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_OUTPUT_RESULT {
+    ($a:expr,$b:expr) => {
+        format!("Game over: {} by {}.", $a, $b)
     };
 }
 #[macro_export]
-macro_rules! FORMAT_OUTPUT_CIRITCAL_ERROR {
+macro_rules! FORMAT_OUTPUT_DRAW_OFFER {
+    ($a:expr,$b:expr) => {
+        format!(
+            "{} offers a draw. {}, enter accept or decline. The offer is withdrawn with {}'s next move.",
+            $a, $b, $a
+        )
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_OUTPUT_ERROR {
     ($a:expr) => {
-        format!("Something went wrong: {:?}", $a)
+        format!("Checkmate! Sorry, you lost, {}!", $a)
+    };
+}
+#[macro_export]
+macro_rules! FORMAT_PROMPT_MOVE {
+    ($a:expr,$b:expr) => {
+        format!("{} to {}", $a, $b)
     };
 }
 
@@ -55,10 +94,25 @@ pub const OUTPUT_INVALID_COMMAND: &str = "Invalid command";
 pub const OUTPUT_ENTER_MOVE: &str = "Press enter to move";
 pub const OUTPUT_HINT_PROMOTE: &str =
     "Piece will be promoted. Select promotion type: (Q)ueen, (R)ook, K(N)ight or (B)ishop.";
-pub const OUTPUT_ILLEGAL_MOVE: &str = "Illegal move";
-pub const OUTPUT_MOVE_ERROR_CHECK: &str =
-    "Cannot make this move because king is in check after this move.";
 pub const OUTPUT_STATE_CHECK: &str = "Check!";
-
-pub const LABEL_WHITE: &str = "White";
-pub const LABEL_BLACK: &str = "Black";
+pub const OUTPUT_STALEMATE: &str = "Stalemate! The game is a draw.";
+pub const OUTPUT_INSUFFICIENT_MATERIAL: &str =
+    "Neither side has enough material to checkmate. The game is a draw.";
+pub const OUTPUT_THREEFOLD_REPETITION: &str = "Draw claimed by threefold repetition.";
+pub const OUTPUT_FIVEFOLD_REPETITION: &str = "Draw by fivefold repetition.";
+pub const OUTPUT_TIMEOUT_DRAW: &str =
+    "Time ran out, but the opponent cannot checkmate. The game is a draw.";
+pub const OUTPUT_FIFTY_MOVE_RULE: &str = "Draw claimed by the fifty-move rule.";
+pub const OUTPUT_SEVENTY_FIVE_MOVE_RULE: &str = "Draw by the seventy-five-move rule.";
+pub const OUTPUT_HINT_CLAIM_REPETITION: &str =
+    "The position occurred three times, enter claim to end the game in a draw.";
+pub const OUTPUT_HINT_CLAIM_FIFTY_MOVE_RULE: &str =
+    "No capture or pawn move in fifty moves, enter claim to end the game in a draw.";
+pub const OUTPUT_HINT_GAME_OVER: &str =
+    "Enter new to start a new game, export to show the final position as FEN or pgn to save the game.";
+pub const OUTPUT_CANNOT_OFFER_DRAW: &str = "A draw cannot be offered right now";
+pub const OUTPUT_CANNOT_RESIGN: &str = "There is no game in progress to resign";
+pub const OUTPUT_NO_DRAW_OFFER: &str = "There is no draw offer";
+pub const OUTPUT_OWN_DRAW_OFFER: &str = "The opponent has to answer your draw offer";
+pub const OUTPUT_DRAW_DECLINED: &str = "Draw offer declined.";
+pub const OUTPUT_NO_DRAW_CLAIM: &str = "There is no draw to claim";
@@ -0,0 +1,16 @@
+pub mod logic;
+
+// The terminal client, services that only need the rules build without it
+#[cfg(feature = "tui")]
+pub mod draw;
+
+pub use logic::basic::{Coordinate, Player};
+pub use logic::board::Board;
+pub use logic::fen::FenError;
+pub use logic::game::{Game, GameState};
+pub use logic::pgn::{read_pgn, GameInfo, PgnError};
+pub use logic::pieces::{Move, MoveError, Piece, PieceType};
+pub use logic::result::{GameResult, Outcome, Termination};
+pub use logic::san::SanError;
+pub use logic::uci::UciError;
+pub use logic::validation::{InvalidPosition, PositionError};
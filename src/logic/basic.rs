@@ -1,9 +1,9 @@
-use crate::draw::text::{LABEL_BLACK, LABEL_WHITE};
 use crate::logic::board::{BOARD_MAX_AXIS, BOARD_SIZE};
-use crate::utils::ValueError;
 
+use std::error::Error;
 use std::fmt;
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FieldColor {
     White,
     Black,
@@ -18,8 +18,8 @@ pub enum Player {
 impl Player {
     pub fn to_label(&self) -> &str {
         match self {
-            Self::White => LABEL_WHITE,
-            Self::Black => LABEL_BLACK,
+            Self::White => "White",
+            Self::Black => "Black",
         }
     }
 
@@ -38,17 +38,28 @@ pub struct Coordinate {
 }
 
 impl Coordinate {
-    pub fn try_new(x: usize, y: usize) -> Result<Self, ValueError> {
+    pub fn try_new(x: usize, y: usize) -> Result<Self, CoordinateError> {
         Ok(Self {
             x: Self::try_axis_bound(x)?,
             y: Self::try_axis_bound(y)?,
         })
     }
 
-    pub fn try_axis_bound(val: usize) -> Result<usize, ValueError> {
+    pub fn try_axis_bound(val: usize) -> Result<usize, CoordinateError> {
         match val {
             x @ 0..=BOARD_MAX_AXIS => Ok(x),
-            _ => Err(ValueError),
+            _ => Err(CoordinateError::OutOfBounds(val)),
+        }
+    }
+
+    pub fn try_from_field_name(name: &str) -> Result<Self, CoordinateError> {
+        let mut chars = name.chars();
+
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(column), Some(row), None) => {
+                Self::try_new(name_to_column(column)?, name_to_row(row)?)
+            }
+            _ => Err(CoordinateError::InvalidFieldName(name.to_string())),
         }
     }
 
@@ -76,6 +87,35 @@ impl Coordinate {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CoordinateError {
+    OutOfBounds(usize),
+    InvalidColumn(char),
+    InvalidRow(char),
+    InvalidFieldName(String),
+}
+
+impl Error for CoordinateError {}
+
+impl fmt::Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds(value) => write!(
+                f,
+                "{} is outside of the board, coordinates range from 0 to {}",
+                value, BOARD_MAX_AXIS
+            ),
+            Self::InvalidColumn(letter) => {
+                write!(f, "'{}' is not a column, expected A to H", letter)
+            }
+            Self::InvalidRow(letter) => write!(f, "'{}' is not a row, expected 1 to 8", letter),
+            Self::InvalidFieldName(name) => {
+                write!(f, "\"{}\" is not a square, expected e.g. E4", name)
+            }
+        }
+    }
+}
+
 impl fmt::Display for Coordinate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({}, {})", self.x, self.y)
@@ -91,3 +131,17 @@ pub fn column_to_name(x: usize) -> String {
     assert!(name.is_ascii_alphanumeric());
     format!("{}", name)
 }
+
+pub fn name_to_row(letter: char) -> Result<usize, CoordinateError> {
+    match letter {
+        c @ '1'..='8' => Ok(BOARD_MAX_AXIS - (c as usize - '1' as usize)),
+        _ => Err(CoordinateError::InvalidRow(letter)),
+    }
+}
+
+pub fn name_to_column(letter: char) -> Result<usize, CoordinateError> {
+    match letter.to_ascii_uppercase() {
+        c @ 'A'..='H' => Ok(c as usize - 'A' as usize),
+        _ => Err(CoordinateError::InvalidColumn(letter)),
+    }
+}
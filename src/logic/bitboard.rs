@@ -0,0 +1,269 @@
+use crate::logic::basic::{Coordinate, Player};
+use crate::logic::board::BOARD_SIZE;
+use crate::logic::pieces::{Piece, PieceType};
+
+// Squares are numbered row by row like `Board::tiles`, so A8 is bit 0 and H1 is bit 63
+pub type Bitboard = u64;
+
+pub const EMPTY: Bitboard = 0;
+
+pub fn square(coordinate: &Coordinate) -> usize {
+    coordinate.yv() * BOARD_SIZE + coordinate.xv()
+}
+
+pub fn bit(coordinate: &Coordinate) -> Bitboard {
+    1 << square(coordinate)
+}
+
+pub fn coordinate(square: usize) -> Coordinate {
+    Coordinate::try_new(square % BOARD_SIZE, square / BOARD_SIZE).unwrap()
+}
+
+pub fn coordinates(bitboard: Bitboard) -> Coordinates {
+    Coordinates(bitboard)
+}
+
+pub struct Coordinates(Bitboard);
+
+impl Iterator for Coordinates {
+    type Item = Coordinate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == EMPTY {
+            return None;
+        }
+
+        let square = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(coordinate(square))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Bitboards {
+    pieces: [[Bitboard; 6]; 2],
+}
+
+impl Bitboards {
+    pub fn pieces(&self, player: &Player, piece_type: &PieceType) -> Bitboard {
+        self.pieces[player_index(player)][piece_type_index(piece_type)]
+    }
+
+    pub fn occupancy(&self, player: &Player) -> Bitboard {
+        self.pieces[player_index(player)]
+            .iter()
+            .fold(EMPTY, |all, pieces| all | pieces)
+    }
+
+    pub fn all(&self) -> Bitboard {
+        self.occupancy(&Player::White) | self.occupancy(&Player::Black)
+    }
+
+    pub fn add(&mut self, coordinate: &Coordinate, piece: &Piece) {
+        self.pieces[player_index(&piece.player)][piece_type_index(&piece.piece_type)] |=
+            bit(coordinate);
+    }
+
+    pub fn remove(&mut self, coordinate: &Coordinate, piece: &Piece) {
+        self.pieces[player_index(&piece.player)][piece_type_index(&piece.piece_type)] &=
+            !bit(coordinate);
+    }
+}
+
+pub fn player_index(player: &Player) -> usize {
+    match player {
+        Player::White => 0,
+        Player::Black => 1,
+    }
+}
+
+pub fn piece_type_index(piece_type: &PieceType) -> usize {
+    match piece_type {
+        PieceType::King => 0,
+        PieceType::Queen => 1,
+        PieceType::Rook => 2,
+        PieceType::Bishop => 3,
+        PieceType::Knight => 4,
+        PieceType::Pawn => 5,
+    }
+}
+
+pub fn knight_attacks(coordinate: &Coordinate) -> Bitboard {
+    KNIGHT_ATTACKS[square(coordinate)]
+}
+
+pub fn king_attacks(coordinate: &Coordinate) -> Bitboard {
+    KING_ATTACKS[square(coordinate)]
+}
+
+pub fn pawn_attacks(player: &Player, coordinate: &Coordinate) -> Bitboard {
+    match player {
+        Player::White => WHITE_PAWN_ATTACKS[square(coordinate)],
+        Player::Black => BLACK_PAWN_ATTACKS[square(coordinate)],
+    }
+}
+
+pub fn rook_attacks(coordinate: &Coordinate, occupancy: Bitboard) -> Bitboard {
+    let square = square(coordinate);
+    ray_attacks(&RAYS_SOUTH, square, occupancy, true)
+        | ray_attacks(&RAYS_EAST, square, occupancy, true)
+        | ray_attacks(&RAYS_NORTH, square, occupancy, false)
+        | ray_attacks(&RAYS_WEST, square, occupancy, false)
+}
+
+pub fn bishop_attacks(coordinate: &Coordinate, occupancy: Bitboard) -> Bitboard {
+    let square = square(coordinate);
+    ray_attacks(&RAYS_SOUTH_EAST, square, occupancy, true)
+        | ray_attacks(&RAYS_SOUTH_WEST, square, occupancy, true)
+        | ray_attacks(&RAYS_NORTH_EAST, square, occupancy, false)
+        | ray_attacks(&RAYS_NORTH_WEST, square, occupancy, false)
+}
+
+pub fn queen_attacks(coordinate: &Coordinate, occupancy: Bitboard) -> Bitboard {
+    rook_attacks(coordinate, occupancy) | bishop_attacks(coordinate, occupancy)
+}
+
+fn ray_attacks(
+    rays: &[Bitboard; BOARD_SIZE * BOARD_SIZE],
+    square: usize,
+    occupancy: Bitboard,
+    ascending: bool,
+) -> Bitboard {
+    // The ray stops at the first blocker, which itself is still attacked
+    let ray = rays[square];
+    let blockers = ray & occupancy;
+
+    if blockers == EMPTY {
+        return ray;
+    }
+
+    let blocker = if ascending {
+        blockers.trailing_zeros()
+    } else {
+        63 - blockers.leading_zeros()
+    } as usize;
+
+    ray & !rays[blocker]
+}
+
+const KNIGHT_ATTACKS: [Bitboard; BOARD_SIZE * BOARD_SIZE] = leaper_table(&[
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+]);
+const KING_ATTACKS: [Bitboard; BOARD_SIZE * BOARD_SIZE] = leaper_table(&[
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+]);
+const WHITE_PAWN_ATTACKS: [Bitboard; BOARD_SIZE * BOARD_SIZE] = leaper_table(&[(-1, -1), (1, -1)]);
+const BLACK_PAWN_ATTACKS: [Bitboard; BOARD_SIZE * BOARD_SIZE] = leaper_table(&[(-1, 1), (1, 1)]);
+
+const RAYS_NORTH: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(0, -1);
+const RAYS_SOUTH: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(0, 1);
+const RAYS_EAST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(1, 0);
+const RAYS_WEST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(-1, 0);
+const RAYS_NORTH_EAST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(1, -1);
+const RAYS_NORTH_WEST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(-1, -1);
+const RAYS_SOUTH_EAST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(1, 1);
+const RAYS_SOUTH_WEST: [Bitboard; BOARD_SIZE * BOARD_SIZE] = ray_table(-1, 1);
+
+const fn leaper_table(deltas: &[(i32, i32)]) -> [Bitboard; BOARD_SIZE * BOARD_SIZE] {
+    let mut table = [EMPTY; BOARD_SIZE * BOARD_SIZE];
+    let mut square = 0;
+
+    while square < BOARD_SIZE * BOARD_SIZE {
+        let mut i = 0;
+        while i < deltas.len() {
+            let x = (square % BOARD_SIZE) as i32 + deltas[i].0;
+            let y = (square / BOARD_SIZE) as i32 + deltas[i].1;
+            if is_on_board(x, y) {
+                table[square] |= 1 << (y as usize * BOARD_SIZE + x as usize);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+
+    table
+}
+
+const fn ray_table(delta_x: i32, delta_y: i32) -> [Bitboard; BOARD_SIZE * BOARD_SIZE] {
+    let mut table = [EMPTY; BOARD_SIZE * BOARD_SIZE];
+    let mut square = 0;
+
+    while square < BOARD_SIZE * BOARD_SIZE {
+        let mut x = (square % BOARD_SIZE) as i32 + delta_x;
+        let mut y = (square / BOARD_SIZE) as i32 + delta_y;
+        while is_on_board(x, y) {
+            table[square] |= 1 << (y as usize * BOARD_SIZE + x as usize);
+            x += delta_x;
+            y += delta_y;
+        }
+        square += 1;
+    }
+
+    table
+}
+
+const fn is_on_board(x: i32, y: i32) -> bool {
+    x >= 0 && x < BOARD_SIZE as i32 && y >= 0 && y < BOARD_SIZE as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::basic::Player;
+    use crate::logic::pieces::tests::c;
+
+    use super::{
+        bishop_attacks, bit, coordinates, king_attacks, knight_attacks, pawn_attacks, rook_attacks,
+        EMPTY,
+    };
+
+    #[test]
+    fn test_leaper_attacks() {
+        assert_eq!(knight_attacks(&c(0, 0)), bit(&c(1, 2)) | bit(&c(2, 1)));
+        assert_eq!(knight_attacks(&c(4, 4)).count_ones(), 8);
+        assert_eq!(king_attacks(&c(7, 7)).count_ones(), 3);
+        assert_eq!(king_attacks(&c(3, 3)).count_ones(), 8);
+
+        assert_eq!(
+            pawn_attacks(&Player::White, &c(4, 6)),
+            bit(&c(3, 5)) | bit(&c(5, 5))
+        );
+        assert_eq!(pawn_attacks(&Player::Black, &c(0, 1)), bit(&c(1, 2)));
+        assert_eq!(pawn_attacks(&Player::White, &c(4, 0)), EMPTY);
+    }
+
+    #[test]
+    fn test_sliding_attacks() {
+        assert_eq!(rook_attacks(&c(0, 7), EMPTY).count_ones(), 14);
+        assert_eq!(bishop_attacks(&c(3, 3), EMPTY).count_ones(), 13);
+
+        // Blockers are attacked, squares behind them are not
+        let occupancy = bit(&c(0, 4)) | bit(&c(3, 7));
+        let attacks = rook_attacks(&c(0, 7), occupancy);
+        assert_eq!(
+            coordinates(attacks).collect::<Vec<_>>(),
+            vec![c(0, 4), c(0, 5), c(0, 6), c(1, 7), c(2, 7), c(3, 7)]
+        );
+
+        let occupancy = bit(&c(1, 1)) | bit(&c(5, 5));
+        let attacks = bishop_attacks(&c(3, 3), occupancy);
+        assert!(attacks & bit(&c(1, 1)) != EMPTY);
+        assert!(attacks & bit(&c(0, 0)) == EMPTY);
+        assert!(attacks & bit(&c(5, 5)) != EMPTY);
+        assert!(attacks & bit(&c(6, 6)) == EMPTY);
+        assert!(attacks & bit(&c(0, 6)) != EMPTY);
+    }
+}
@@ -1,61 +1,187 @@
-use crate::logic::basic::{Coordinate, Player};
-use crate::logic::pieces::Piece;
-use crate::utils::DiscreetUnwrap;
+use crate::logic::basic::{Coordinate, FieldColor, Player};
+use crate::logic::bitboard::{self, Bitboard, Bitboards, EMPTY};
+use crate::logic::castling::CastlingRights;
+use crate::logic::pieces::{InvalidPieceLetter, Piece};
+use crate::logic::validation::{InvalidPosition, PositionError};
+use crate::logic::zobrist::{self, ZobristKey};
 
-use super::pieces::PieceType;
+use std::hash::{Hash, Hasher};
+
+use super::pieces::{Move, MoveError, PieceType};
 
 pub const BOARD_SIZE: usize = 8;
 pub const BOARD_MAX_AXIS: usize = BOARD_SIZE - 1;
 
 #[derive(Clone)]
 pub struct Board {
-    pub tiles: [[TileContent; BOARD_SIZE]; BOARD_SIZE],
+    tiles: [[TileContent; BOARD_SIZE]; BOARD_SIZE],
+    bitboards: Bitboards,
+    placement_key: ZobristKey,
     pub turn: Player,
     pub en_passant: Option<Coordinate>,
+    pub castling: CastlingRights,
+    pub halfmove_clock: u32,
+    pub fullmove_number: u32,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::from_configuration(DEFAULT_PIECE_CONFIGURATION).unwrap()
+    }
 }
 
 impl Board {
-    pub fn default() -> Self {
-        Self::from_configuration(DEFAULT_PIECE_CONFIGURATION)
+    pub fn from_configuration(
+        configuration: [[char; BOARD_SIZE]; BOARD_SIZE],
+    ) -> Result<Self, InvalidPosition> {
+        let mut errors = Vec::new();
+        let mut tiles: [[TileContent; BOARD_SIZE]; BOARD_SIZE] = Default::default();
+
+        for (y, row) in configuration.into_iter().enumerate() {
+            for (x, letter) in row.into_iter().enumerate() {
+                match TileContent::from_letter(letter) {
+                    Ok(tile) => tiles[y][x] = tile,
+                    Err(_) => errors.push(PositionError::InvalidPiece(letter)),
+                }
+            }
+        }
+
+        let board = Self::from_tiles(tiles);
+
+        // Report unknown letters together with everything wrong with the rest of the board
+        if let Err(InvalidPosition(position_errors)) = board.validate() {
+            errors.extend(position_errors);
+        }
+
+        if errors.is_empty() {
+            Ok(board)
+        } else {
+            Err(InvalidPosition(errors))
+        }
     }
 
-    pub fn from_configuration(configuration: [[char; BOARD_SIZE]; BOARD_SIZE]) -> Self {
-        let tiles = configuration
-            .into_iter()
-            .map(|row| {
-                row.into_iter()
-                    .map(TileContent::from_letter)
-                    .collect::<Vec<_>>()
-                    .try_into()
-                    .duwrp()
-            })
-            .collect::<Vec<_>>()
-            .try_into()
-            .duwrp();
-
-        Self {
-            tiles,
+    pub fn from_tiles(tiles: [[TileContent; BOARD_SIZE]; BOARD_SIZE]) -> Self {
+        let castling = CastlingRights::from_placement(&tiles);
+
+        let mut board = Self {
+            tiles: Default::default(),
+            bitboards: Bitboards::default(),
+            placement_key: 0,
             turn: Player::White,
             en_passant: None,
+            castling,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        };
+
+        for (y, row) in tiles.into_iter().enumerate() {
+            for (x, tile) in row.into_iter().enumerate() {
+                board.set_tile(&Coordinate::try_new(x, y).unwrap(), tile);
+            }
         }
+
+        board
     }
 
     pub fn turned(&self) -> Self {
         let mut new_board = self.clone();
         new_board.turn = self.turn.other();
         new_board.en_passant = None;
+        new_board.halfmove_clock += 1;
+        if self.turn == Player::Black {
+            new_board.fullmove_number += 1;
+        }
         new_board
     }
 
+    pub fn made(&self, a_move: &Move) -> Self {
+        let mut new_board = self.clone();
+        new_board.make_move(a_move);
+        new_board
+    }
+
+    pub fn make_move(&mut self, a_move: &Move) -> UndoInfo {
+        // The move is not validated, see `Piece::check_move`
+        let undo = UndoInfo {
+            en_passant: self.en_passant.take(),
+            castling: self.castling.clone(),
+            halfmove_clock: self.halfmove_clock,
+        };
+
+        // Pawn moves and captures reset the fifty-move count
+        if a_move.piece.piece_type == PieceType::Pawn || a_move.is_capture() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        self.clear_tile(&a_move.captured_coordinate());
+        self.move_tile(&a_move.from, &a_move.to);
+
+        if let Some(new_type) = &a_move.promotion {
+            let promoted = a_move.piece.promoted(new_type.clone());
+            self.set_tile(&a_move.to, TileContent::Piece(promoted));
+        }
+
+        if let Some((rook_from, rook_to)) = a_move.castling_rook() {
+            self.move_tile(&rook_from, &rook_to);
+        }
+
+        if a_move.is_double_push() {
+            self.en_passant = Some(a_move.to.clone());
+        }
+
+        if self.turn == Player::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.other();
+
+        undo
+    }
+
+    pub fn unmake_move(&mut self, a_move: &Move, undo: UndoInfo) {
+        self.turn = self.turn.other();
+        if self.turn == Player::Black {
+            self.fullmove_number -= 1;
+        }
+
+        if let Some((rook_from, rook_to)) = a_move.castling_rook() {
+            let rook = self.get_tile(&rook_to).clone();
+            self.clear_tile(&rook_to);
+            self.set_tile(&rook_from, rook);
+        }
+
+        self.clear_tile(&a_move.to);
+        self.set_tile(&a_move.from, TileContent::Piece(a_move.piece.clone()));
+
+        if let Some(captured) = &a_move.captured {
+            self.set_tile(
+                &a_move.captured_coordinate(),
+                TileContent::Piece(captured.clone()),
+            );
+        }
+
+        self.en_passant = undo.en_passant;
+        self.castling = undo.castling;
+        self.halfmove_clock = undo.halfmove_clock;
+    }
+
     pub fn get_tile(&self, coordinate: &Coordinate) -> &TileContent {
         &self.tiles[coordinate.yv()][coordinate.xv()]
     }
 
+    pub fn tiles(&self) -> &[[TileContent; BOARD_SIZE]; BOARD_SIZE] {
+        &self.tiles
+    }
+
+    pub(crate) fn bitboards(&self) -> &Bitboards {
+        &self.bitboards
+    }
+
     pub fn move_tile(&mut self, from: &Coordinate, to: &Coordinate) {
-        let from_tile = match self.get_tile(from) {
-            TileContent::Empty => TileContent::Empty,
-            TileContent::Piece(piece) => TileContent::Piece(piece.moved()),
-        };
+        let from_tile = self.get_tile(from).clone();
+        self.castling.update_for_square(from);
+        self.castling.update_for_square(to);
         self.clear_tile(from);
         self.set_tile(to, from_tile);
     }
@@ -65,98 +191,286 @@ impl Board {
     }
 
     pub fn set_tile(&mut self, coordinate: &Coordinate, new_tile: TileContent) {
+        // Both representations have to stay in sync
+        if let TileContent::Piece(piece) = &self.tiles[coordinate.yv()][coordinate.xv()] {
+            self.bitboards.remove(coordinate, piece);
+            self.placement_key ^= zobrist::piece(coordinate, piece);
+        }
+        if let TileContent::Piece(piece) = &new_tile {
+            self.bitboards.add(coordinate, piece);
+            self.placement_key ^= zobrist::piece(coordinate, piece);
+        }
         self.tiles[coordinate.yv()][coordinate.xv()] = new_tile;
     }
 
-    pub fn is_current_player_checkmate(&self) -> bool {
-        let piece_coords = self.find_own_pieces(&self.turn);
-
-        for coord in piece_coords {
-            if let TileContent::Piece(piece) = self.get_tile(&coord) {
-                for a_move in piece.all_moves(self, &coord) {
-                    let new_board = piece.move_piece(self, &coord, &a_move).unwrap();
-                    if !new_board.is_player_on_check(&self.turn) {
-                        return false;
-                    }
-                }
-            }
+    pub fn hash(&self) -> ZobristKey {
+        // Placement is kept up to date by `set_tile`, the remaining state is cheap to fold in
+        self.placement_key
+            ^ zobrist::turn(&self.turn)
+            ^ zobrist::castling(&self.castling)
+            ^ zobrist::en_passant(self.capturable_en_passant().as_ref())
+    }
+
+    pub(crate) fn en_passant_target(&self) -> Option<Coordinate> {
+        // The square the opponent's pawn skipped, if the en passant pawn is really there
+        let pawn = self.en_passant.as_ref()?;
+        match self.get_tile(pawn) {
+            TileContent::Piece(piece)
+                if piece.piece_type == PieceType::Pawn && piece.player != self.turn => {}
+            // The field is public, an unvalidated board may point anywhere
+            _ => return None,
         }
 
-        true
+        let target_y = match self.turn {
+            Player::White => pawn.yv().checked_sub(1)?,
+            Player::Black => pawn.yv() + 1,
+        };
+        Coordinate::try_new(pawn.xv(), target_y).ok()
     }
 
-    pub fn is_player_on_check(&self, player: &Player) -> bool {
-        let (king_coord, enemy_coords) = self.find_pieces_for_check(player);
+    fn capturable_en_passant(&self) -> Option<Coordinate> {
+        // En passant only counts as a right if the side to move can actually capture
+        let target = self.en_passant_target()?;
+        let pawn = self.en_passant.as_ref()?;
+        let candidates = bitboard::pawn_attacks(&self.turn.other(), &target)
+            & self.bitboards.pieces(&self.turn, &PieceType::Pawn);
 
-        // TODO: Refactor this soo we don't need to copy entire board for every check.
-        let board = if *player == self.turn {
-            self.turned()
-        } else {
-            self.clone()
+        bitboard::coordinates(candidates)
+            .any(|from| self.en_passant_keeps_king_safe(&from, &target, pawn))
+            .then(|| pawn.clone())
+    }
+
+    fn en_passant_keeps_king_safe(
+        &self,
+        from: &Coordinate,
+        target: &Coordinate,
+        pawn: &Coordinate,
+    ) -> bool {
+        // Only the three squares of the capture change, so the position is not copied
+        let king = match self.find_king(&self.turn) {
+            Some(king) => king,
+            None => return true,
         };
+        let occupancy = (self.bitboards.all() & !bitboard::bit(from) & !bitboard::bit(pawn))
+            | bitboard::bit(target);
 
-        for enemy_coord in enemy_coords {
-            if let TileContent::Piece(piece) = self.get_tile(&enemy_coord) {
-                if piece.can_move(&board, &enemy_coord, &king_coord) {
-                    return true;
-                }
+        self.attackers_in(&king, &self.turn.other(), occupancy) & !bitboard::bit(pawn) == EMPTY
+    }
+
+    pub fn is_current_player_checkmate(&self) -> bool {
+        self.is_player_on_check(&self.turn) && !self.has_legal_move()
+    }
+
+    pub fn is_current_player_stalemate(&self) -> bool {
+        !self.is_player_on_check(&self.turn) && !self.has_legal_move()
+    }
+
+    fn has_legal_move(&self) -> bool {
+        let mut scratch = self.clone();
+
+        self.find_own_pieces(&self.turn).iter().any(|coord| {
+            self.pseudo_legal_moves_from(coord)
+                .iter()
+                .any(|a_move| scratch.keeps_king_safe(a_move))
+        })
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut scratch = self.clone();
+
+        self.find_own_pieces(&self.turn)
+            .iter()
+            .flat_map(|coord| self.pseudo_legal_moves_from(coord))
+            .filter(|a_move| scratch.keeps_king_safe(a_move))
+            .collect()
+    }
+
+    pub fn legal_moves_from(&self, from: &Coordinate) -> Vec<Move> {
+        let mut scratch = self.clone();
+
+        self.pseudo_legal_moves_from(from)
+            .into_iter()
+            .filter(|a_move| scratch.keeps_king_safe(a_move))
+            .collect()
+    }
+
+    fn pseudo_legal_moves_from(&self, from: &Coordinate) -> Vec<Move> {
+        match self.get_tile(from) {
+            TileContent::Piece(piece) if piece.player == self.turn => piece.all_moves(self, from),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn is_legal_move(&self, a_move: &Move) -> bool {
+        match self.get_tile(&a_move.from) {
+            TileContent::Piece(piece) => {
+                piece.check_move(self, a_move).is_ok() && self.clone().keeps_king_safe(a_move)
             }
+            TileContent::Empty => false,
         }
+    }
 
-        false
+    fn keeps_king_safe(&mut self, a_move: &Move) -> bool {
+        let player = self.turn.clone();
+        let undo = self.make_move(a_move);
+        let is_safe = !self.is_player_on_check(&player);
+        self.unmake_move(a_move, undo);
+        is_safe
     }
 
-    fn find_own_pieces(&self, player: &Player) -> Vec<Coordinate> {
-        let mut coords: Vec<Coordinate> = Vec::new();
-
-        for y in 0..BOARD_SIZE {
-            for x in 0..BOARD_SIZE {
-                if let TileContent::Piece(piece) = &self.tiles[y][x] {
-                    if piece.player == *player {
-                        coords.push(Coordinate::try_new(x, y).unwrap());
-                    }
+    pub fn apply_move(&self, a_move: &Move) -> Result<Self, MoveError> {
+        let new_board = match self.get_tile(&a_move.from) {
+            TileContent::Piece(piece) => piece.move_piece(self, a_move),
+            TileContent::Empty => Err(MoveError::EmptySquare(a_move.from.clone())),
+        }?;
+
+        match new_board.checkers_of(&self.turn).into_iter().next() {
+            Some(checker) => match new_board.get_tile(&checker) {
+                TileContent::Piece(piece) => Err(MoveError::IsCheck(
+                    piece.piece_type.clone(),
+                    checker.clone(),
+                )),
+                TileContent::Empty => unreachable!("Checkers are taken from the occupied squares"),
+            },
+            None => Ok(new_board),
+        }
+    }
+
+    pub fn is_insufficient_material(&self) -> bool {
+        // Positions in which no sequence of legal moves can lead to checkmate:
+        // lone kings, a single minor piece, or only bishops on one square color
+        let mut knights = 0;
+        let mut bishops = EMPTY;
+
+        for player in [Player::White, Player::Black] {
+            for piece_type in [PieceType::Queen, PieceType::Rook, PieceType::Pawn] {
+                if self.bitboards.pieces(&player, &piece_type) != EMPTY {
+                    return false;
                 }
             }
+
+            knights += self
+                .bitboards
+                .pieces(&player, &PieceType::Knight)
+                .count_ones();
+            bishops |= self.bitboards.pieces(&player, &PieceType::Bishop);
+        }
+
+        let bishop_colors: Vec<FieldColor> = bitboard::coordinates(bishops)
+            .map(|coord| coord.get_field_color())
+            .collect();
+
+        match (knights, bishop_colors.first()) {
+            (0, None) => true,
+            (1, None) => true,
+            (0, Some(first)) => bishop_colors.iter().all(|color| color == first),
+            _ => false,
         }
+    }
 
-        coords
+    pub fn has_only_king(&self, player: &Player) -> bool {
+        self.bitboards.occupancy(player) == self.bitboards.pieces(player, &PieceType::King)
     }
 
-    fn find_pieces_for_check(&self, king_player: &Player) -> (Coordinate, Vec<Coordinate>) {
-        let mut king_coord: Option<Coordinate> = None;
-        let mut enemy_coords: Vec<Coordinate> = Vec::new();
+    pub fn is_player_on_check(&self, player: &Player) -> bool {
+        self.find_king(player)
+            .is_some_and(|king_coord| self.is_square_attacked(&king_coord, &player.other()))
+    }
 
-        for y in 0..BOARD_SIZE {
-            for x in 0..BOARD_SIZE {
-                if let TileContent::Piece(piece) = &self.tiles[y][x] {
-                    if piece.player == *king_player {
-                        if matches!(piece.piece_type, PieceType::King) {
-                            king_coord = Some(Coordinate::try_new(x, y).unwrap());
-                        }
-                    } else {
-                        enemy_coords.push(Coordinate::try_new(x, y).unwrap())
-                    }
-                }
+    pub fn is_square_attacked(&self, coordinate: &Coordinate, by_player: &Player) -> bool {
+        self.attackers(coordinate, by_player) != EMPTY
+    }
+
+    pub fn attackers_of(&self, coordinate: &Coordinate) -> Vec<Coordinate> {
+        // Pieces of both players, so the owner of the square sees its defenders too
+        let attackers =
+            self.attackers(coordinate, &Player::White) | self.attackers(coordinate, &Player::Black);
+        bitboard::coordinates(attackers).collect()
+    }
+
+    pub fn checkers(&self) -> Vec<Coordinate> {
+        self.checkers_of(&self.turn)
+    }
+
+    fn checkers_of(&self, player: &Player) -> Vec<Coordinate> {
+        match self.find_king(player) {
+            Some(king_coord) => {
+                bitboard::coordinates(self.attackers(&king_coord, &player.other())).collect()
             }
+            None => Vec::new(),
         }
+    }
+
+    fn attackers(&self, coordinate: &Coordinate, by_player: &Player) -> Bitboard {
+        self.attackers_in(coordinate, by_player, self.bitboards.all())
+    }
+
+    fn attackers_in(
+        &self,
+        coordinate: &Coordinate,
+        by_player: &Player,
+        occupancy: Bitboard,
+    ) -> Bitboard {
+        // Look from the attacked square outwards with the attack pattern of each piece type
+        let pieces = |piece_type| self.bitboards.pieces(by_player, &piece_type);
 
-        (king_coord.unwrap(), enemy_coords)
+        bitboard::pawn_attacks(&by_player.other(), coordinate) & pieces(PieceType::Pawn)
+            | bitboard::knight_attacks(coordinate) & pieces(PieceType::Knight)
+            | bitboard::king_attacks(coordinate) & pieces(PieceType::King)
+            | bitboard::rook_attacks(coordinate, occupancy)
+                & (pieces(PieceType::Rook) | pieces(PieceType::Queen))
+            | bitboard::bishop_attacks(coordinate, occupancy)
+                & (pieces(PieceType::Bishop) | pieces(PieceType::Queen))
+    }
+
+    fn find_own_pieces(&self, player: &Player) -> Vec<Coordinate> {
+        bitboard::coordinates(self.bitboards.occupancy(player)).collect()
+    }
+
+    fn find_king(&self, king_player: &Player) -> Option<Coordinate> {
+        bitboard::coordinates(self.bitboards.pieces(king_player, &PieceType::King)).next()
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UndoInfo {
+    en_passant: Option<Coordinate>,
+    castling: CastlingRights,
+    halfmove_clock: u32,
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        // Same position no matter how it was reached, so move counters are ignored
+        self.placement_key == other.placement_key
+            && self.tiles == other.tiles
+            && self.turn == other.turn
+            && self.castling == other.castling
+            && self.capturable_en_passant() == other.capturable_en_passant()
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(Board::hash(self));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum TileContent {
+    #[default]
     Empty,
     Piece(Piece),
 }
 
 impl TileContent {
-    pub fn from_letter(letter: char) -> Self {
+    pub fn from_letter(letter: char) -> Result<Self, InvalidPieceLetter> {
         if letter == ' ' {
-            return Self::Empty;
+            return Ok(Self::Empty);
         }
 
-        Self::Piece(Piece::from_letter(letter).unwrap())
+        Ok(Self::Piece(Piece::from_letter(letter)?))
     }
 }
 
@@ -170,3 +484,217 @@ const DEFAULT_PIECE_CONFIGURATION: [[char; BOARD_SIZE]; BOARD_SIZE] = [
     ['P', 'P', 'P', 'P', 'P', 'P', 'P', 'P'],
     ['R', 'N', 'B', 'Q', 'K', 'B', 'N', 'R'],
 ];
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::logic::basic::Player;
+    use crate::logic::castling::CastlingRights;
+    use crate::logic::pieces::tests::{c, m};
+    use crate::logic::pieces::{Move, PieceType};
+
+    use super::Board;
+
+    #[test]
+    fn test_legal_moves() {
+        let board = Board::default();
+        assert_eq!(board.legal_moves().len(), 20);
+        assert_eq!(board.legal_moves_from(&c(6, 7)).len(), 2);
+        assert!(board.legal_moves_from(&c(4, 7)).is_empty());
+        // Opponent pieces and empty squares cannot move
+        assert!(board.legal_moves_from(&c(4, 1)).is_empty());
+        assert!(board.legal_moves_from(&c(4, 4)).is_empty());
+
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        assert_eq!(board.legal_moves().len(), 48);
+    }
+
+    #[test]
+    fn test_legal_moves_pinned() {
+        // The bishop is pinned to its king and may only move along the pin
+        let board = Board::from_fen("4k3/8/8/8/q7/8/2B5/3K4 w - - 0 1").unwrap();
+        let moves = board.legal_moves_from(&c(2, 6));
+        assert_eq!(moves.len(), 2);
+        assert!(moves.contains(&m(&board, c(2, 6), c(1, 5))));
+        assert!(moves.contains(&m(&board, c(2, 6), c(0, 4))));
+    }
+
+    #[test]
+    fn test_legal_moves_check() {
+        // Only moves that resolve the check remain
+        let board = Board::from_fen("4k3/8/8/8/8/2N5/3P4/r3K3 w - - 0 1").unwrap();
+        let mut moves = board.legal_moves();
+        moves.sort_by_key(|a_move| (a_move.from.values(), a_move.to.values()));
+        assert_eq!(
+            moves,
+            vec![
+                m(&board, c(2, 5), c(1, 7)),
+                m(&board, c(2, 5), c(3, 7)),
+                m(&board, c(4, 7), c(4, 6)),
+                m(&board, c(4, 7), c(5, 6)),
+            ]
+        );
+        assert!(!board.is_legal_move(&m(&board, c(3, 6), c(3, 5))));
+    }
+
+    #[test]
+    fn test_square_attacks() {
+        let board = Board::from_fen("4k3/8/8/1n6/1Q2p3/8/3P4/R3K3 w - - 0 1").unwrap();
+
+        assert!(board.is_square_attacked(&c(2, 5), &Player::White));
+        assert!(board.is_square_attacked(&c(2, 5), &Player::Black));
+        assert!(board.is_square_attacked(&c(0, 0), &Player::White));
+        // The pawn on e4 blocks the queen
+        assert!(!board.is_square_attacked(&c(5, 4), &Player::White));
+        assert!(!board.is_square_attacked(&c(7, 0), &Player::Black));
+
+        let mut attackers = board.attackers_of(&c(2, 5));
+        attackers.sort_by_key(|coord| coord.values());
+        assert_eq!(attackers, vec![c(1, 3), c(1, 4), c(3, 6)]);
+
+        assert!(board.checkers().is_empty());
+        let board = Board::from_fen("4k3/8/8/8/8/5n2/3P4/R3K2r w - - 0 1").unwrap();
+        let mut checkers = board.checkers();
+        checkers.sort_by_key(|coord| coord.values());
+        assert_eq!(checkers, vec![c(5, 5), c(7, 7)]);
+    }
+
+    #[test]
+    fn test_hash() {
+        let board = Board::default();
+        let mut transposed = board.clone();
+
+        // Knights out and back again reach the same position with other counters
+        for (from, to) in [
+            (c(6, 7), c(5, 5)),
+            (c(6, 0), c(5, 2)),
+            (c(5, 5), c(6, 7)),
+            (c(5, 2), c(6, 0)),
+        ] {
+            transposed = transposed.apply_move(&m(&transposed, from, to)).unwrap();
+        }
+        assert_eq!(transposed.hash(), board.hash());
+        assert!(transposed == board);
+        assert_eq!(transposed.fullmove_number, 3);
+
+        let positions: HashSet<Board> = [board.clone(), transposed].into_iter().collect();
+        assert_eq!(positions.len(), 1);
+
+        let mut turned = board.clone();
+        turned.turn = Player::Black;
+        assert_ne!(turned.hash(), board.hash());
+        assert!(turned != board);
+
+        let mut no_castling = board.clone();
+        no_castling.castling = CastlingRights::none();
+        assert_ne!(no_castling.hash(), board.hash());
+    }
+
+    #[test]
+    fn test_hash_invalid_en_passant() {
+        // Boards built from tiles are not validated
+        let board = Board::default();
+        for (turn, en_passant) in [
+            (Player::White, c(0, 0)),
+            (Player::Black, c(7, 7)),
+            (Player::White, c(4, 4)),
+            (Player::White, c(4, 6)),
+        ] {
+            let mut invalid = board.clone();
+            invalid.turn = turn.clone();
+            invalid.en_passant = Some(en_passant);
+
+            let mut expected = board.clone();
+            expected.turn = turn;
+            assert_eq!(invalid.hash(), expected.hash());
+        }
+    }
+
+    #[test]
+    fn test_hash_en_passant() {
+        // Only captures that are legal make the en passant square part of the position
+        for (fen, capturable) in [
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", true),
+            ("4k3/8/8/8/3Pp3/8/8/4K3 b - d3 0 1", true),
+            ("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1", false),
+            ("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1", false),
+            ("8/8/8/3pP3/4K3/8/8/7k w - d6 0 1", true),
+            ("4k3/8/8/3pP3/8/8/8/4K2r w - d6 0 1", false),
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert_eq!(
+                board.capturable_en_passant().is_some(),
+                capturable,
+                "{}",
+                fen
+            );
+            assert_eq!(
+                board.legal_moves().iter().any(Move::is_en_passant),
+                capturable,
+                "{}",
+                fen
+            );
+
+            let mut without = board.clone();
+            without.en_passant = None;
+            assert_eq!(board.hash() == without.hash(), !capturable, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn test_move_counters() {
+        let board = Board::from_fen("4k3/8/2p5/4N3/8/8/4P3/4K3 w - - 12 30").unwrap();
+
+        // Quiet piece moves count towards the fifty-move rule
+        let quiet = board.made(&m(&board, c(4, 3), c(5, 1)));
+        assert_eq!(quiet.halfmove_clock, 13);
+        assert_eq!(quiet.fullmove_number, 30);
+
+        // Pawn moves and captures reset the count
+        assert_eq!(board.made(&m(&board, c(4, 6), c(4, 4))).halfmove_clock, 0);
+        assert_eq!(board.made(&m(&board, c(4, 3), c(2, 2))).halfmove_clock, 0);
+
+        // The fullmove number grows after Black's move
+        let answered = quiet.made(&m(&quiet, c(4, 0), c(3, 1)));
+        assert_eq!(answered.halfmove_clock, 14);
+        assert_eq!(answered.fullmove_number, 31);
+    }
+
+    #[test]
+    fn test_make_unmake_move() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        ] {
+            let mut board = Board::from_fen(fen).unwrap();
+
+            for a_move in board.legal_moves() {
+                let expected = board.apply_move(&a_move).unwrap().to_fen();
+
+                let undo = board.make_move(&a_move);
+                assert_eq!(board.to_fen(), expected, "{}", a_move);
+                let from_scratch = Board::from_fen(&expected).unwrap();
+                assert_eq!(board.hash(), from_scratch.hash(), "{}", a_move);
+                board.unmake_move(&a_move, undo);
+                assert_eq!(board.to_fen(), fen, "{}", a_move);
+            }
+
+            // Bitboards follow the tiles through every move
+            assert_eq!(board.bitboards(), Board::from_fen(fen).unwrap().bitboards());
+        }
+    }
+
+    #[test]
+    fn test_legal_moves_promotion() {
+        let board = Board::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let moves = board.legal_moves_from(&c(0, 1));
+        assert_eq!(moves.len(), 4);
+        let promotion = Move::new(&board, &c(0, 1), &c(0, 0), Some(PieceType::Knight)).unwrap();
+        assert!(moves.contains(&promotion));
+        assert!(moves.iter().all(|a_move| a_move.is_promotion()));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::logic::basic::{Coordinate, Player};
+use crate::logic::board::{TileContent, BOARD_MAX_AXIS, BOARD_SIZE};
+use crate::logic::pieces::PieceType;
+
+pub const KING_HOME_X: usize = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CastlingSide {
+    KingSide,
+    QueenSide,
+}
+
+impl CastlingSide {
+    pub fn all() -> [Self; 2] {
+        [Self::KingSide, Self::QueenSide]
+    }
+
+    pub fn from_king_target(from: &Coordinate, to: &Coordinate) -> Self {
+        if to.xv() > from.xv() {
+            Self::KingSide
+        } else {
+            Self::QueenSide
+        }
+    }
+
+    pub fn to_label(&self) -> &str {
+        match self {
+            Self::KingSide => "king side",
+            Self::QueenSide => "queen side",
+        }
+    }
+
+    pub fn rook_x(&self) -> usize {
+        match self {
+            Self::KingSide => BOARD_MAX_AXIS,
+            Self::QueenSide => 0,
+        }
+    }
+
+    pub fn rook_home(&self, player: &Player) -> Coordinate {
+        Coordinate::try_new(self.rook_x(), home_row(player)).unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CastlingRights {
+    white_king_side: bool,
+    white_queen_side: bool,
+    black_king_side: bool,
+    black_queen_side: bool,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self {
+            white_king_side: true,
+            white_queen_side: true,
+            black_king_side: true,
+            black_queen_side: true,
+        }
+    }
+}
+
+impl CastlingRights {
+    pub fn none() -> Self {
+        Self {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+        }
+    }
+
+    pub fn from_placement(tiles: &[[TileContent; BOARD_SIZE]; BOARD_SIZE]) -> Self {
+        let mut rights = Self::none();
+
+        for player in [Player::White, Player::Black] {
+            let y = home_row(&player);
+
+            for side in CastlingSide::all() {
+                let has_pieces = is_piece_at(tiles, KING_HOME_X, y, &player, PieceType::King)
+                    && is_piece_at(tiles, side.rook_x(), y, &player, PieceType::Rook);
+                rights.set(&player, &side, has_pieces);
+            }
+        }
+
+        rights
+    }
+
+    pub fn has(&self, player: &Player, side: &CastlingSide) -> bool {
+        match (player, side) {
+            (Player::White, CastlingSide::KingSide) => self.white_king_side,
+            (Player::White, CastlingSide::QueenSide) => self.white_queen_side,
+            (Player::Black, CastlingSide::KingSide) => self.black_king_side,
+            (Player::Black, CastlingSide::QueenSide) => self.black_queen_side,
+        }
+    }
+
+    pub fn has_any(&self, player: &Player) -> bool {
+        CastlingSide::all()
+            .iter()
+            .any(|side| self.has(player, side))
+    }
+
+    pub fn set(&mut self, player: &Player, side: &CastlingSide, value: bool) {
+        let right = match (player, side) {
+            (Player::White, CastlingSide::KingSide) => &mut self.white_king_side,
+            (Player::White, CastlingSide::QueenSide) => &mut self.white_queen_side,
+            (Player::Black, CastlingSide::KingSide) => &mut self.black_king_side,
+            (Player::Black, CastlingSide::QueenSide) => &mut self.black_queen_side,
+        };
+        *right = value;
+    }
+
+    pub fn revoke(&mut self, player: &Player, side: &CastlingSide) {
+        self.set(player, side, false);
+    }
+
+    pub fn revoke_all(&mut self, player: &Player) {
+        for side in CastlingSide::all() {
+            self.revoke(player, &side);
+        }
+    }
+
+    pub fn update_for_square(&mut self, coordinate: &Coordinate) {
+        // Anything moving from or onto a home square of king or rook
+        // means that the piece has moved or was captured
+        for player in [Player::White, Player::Black] {
+            if coordinate.yv() != home_row(&player) {
+                continue;
+            }
+
+            if coordinate.xv() == KING_HOME_X {
+                self.revoke_all(&player);
+            }
+
+            for side in CastlingSide::all() {
+                if coordinate.xv() == side.rook_x() {
+                    self.revoke(&player, &side);
+                }
+            }
+        }
+    }
+}
+
+pub fn home_row(player: &Player) -> usize {
+    match player {
+        Player::White => BOARD_MAX_AXIS,
+        Player::Black => 0,
+    }
+}
+
+pub fn king_home(player: &Player) -> Coordinate {
+    Coordinate::try_new(KING_HOME_X, home_row(player)).unwrap()
+}
+
+fn is_piece_at(
+    tiles: &[[TileContent; BOARD_SIZE]; BOARD_SIZE],
+    x: usize,
+    y: usize,
+    player: &Player,
+    piece_type: PieceType,
+) -> bool {
+    match &tiles[y][x] {
+        TileContent::Piece(piece) => piece.player == *player && piece.piece_type == piece_type,
+        TileContent::Empty => false,
+    }
+}
@@ -0,0 +1,191 @@
+use crate::logic::basic::Player;
+
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TimeControl {
+    pub base: Duration,
+    pub increment: Duration,
+}
+
+impl TimeControl {
+    pub fn parse(text: &str) -> Result<Self, InvalidTimeControl> {
+        // <minutes>[+<increment in seconds>], e.g. 5+3
+        let error = || InvalidTimeControl(text.to_string());
+        let (minutes, seconds) = text.split_once('+').unwrap_or((text, "0"));
+
+        let minutes: f64 = minutes.parse().map_err(|_| error())?;
+        let seconds: u64 = seconds.parse().map_err(|_| error())?;
+
+        if !minutes.is_finite() || minutes <= 0.0 {
+            return Err(error());
+        }
+
+        Ok(Self {
+            base: Duration::try_from_secs_f64(minutes * 60.0).map_err(|_| error())?,
+            increment: Duration::from_secs(seconds),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidTimeControl(pub String);
+
+impl Error for InvalidTimeControl {}
+
+impl fmt::Display for InvalidTimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Time control \"{}\" must be minutes with an optional increment in seconds, e.g. 5+3",
+            self.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Clock {
+    control: TimeControl,
+    white: Duration,
+    black: Duration,
+    running: Option<(Player, Instant)>,
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Self {
+        Self {
+            white: control.base,
+            black: control.base,
+            control,
+            running: None,
+        }
+    }
+
+    pub fn control(&self) -> &TimeControl {
+        &self.control
+    }
+
+    pub fn start(&mut self, player: &Player, now: Instant) {
+        self.running = Some((player.clone(), now));
+    }
+
+    pub fn stop(&mut self, now: Instant) {
+        if let Some((player, _)) = self.running.clone() {
+            *self.time_mut(&player) = self.remaining(&player, now);
+            self.running = None;
+        }
+    }
+
+    pub fn press(&mut self, now: Instant) {
+        // The player who moved gets the increment, the opponent's time starts running
+        if let Some((player, _)) = self.running.clone() {
+            self.stop(now);
+            let increment = self.control.increment;
+            let time = self.time_mut(&player);
+            *time = time.saturating_add(increment);
+            self.start(&player.other(), now);
+        }
+    }
+
+    pub fn remaining(&self, player: &Player, now: Instant) -> Duration {
+        let time = match player {
+            Player::White => self.white,
+            Player::Black => self.black,
+        };
+
+        match &self.running {
+            Some((running, since)) if running == player => {
+                time.saturating_sub(now.saturating_duration_since(*since))
+            }
+            _ => time,
+        }
+    }
+
+    pub fn is_flagged(&self, player: &Player, now: Instant) -> bool {
+        self.remaining(player, now).is_zero()
+    }
+
+    fn time_mut(&mut self, player: &Player) -> &mut Duration {
+        match player {
+            Player::White => &mut self.white,
+            Player::Black => &mut self.black,
+        }
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::logic::basic::Player;
+
+    use super::{format_duration, Clock, InvalidTimeControl, TimeControl};
+
+    #[test]
+    fn test_parse_time_control() {
+        assert_eq!(
+            TimeControl::parse("5+3"),
+            Ok(TimeControl {
+                base: Duration::from_secs(300),
+                increment: Duration::from_secs(3),
+            })
+        );
+        assert_eq!(
+            TimeControl::parse("0.5").map(|control| control.base),
+            Ok(Duration::from_secs(30))
+        );
+        for text in ["", "0", "-1", "5+", "+3", "a+b", "1e20", "1e300"] {
+            assert_eq!(
+                TimeControl::parse(text),
+                Err(InvalidTimeControl(text.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut clock = Clock::new(TimeControl::parse("1+2").unwrap());
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+
+        clock.start(&Player::White, start);
+        assert_eq!(
+            clock.remaining(&Player::White, at(10)),
+            Duration::from_secs(50)
+        );
+        assert_eq!(
+            clock.remaining(&Player::Black, at(10)),
+            Duration::from_secs(60)
+        );
+
+        clock.press(at(10));
+        assert_eq!(
+            clock.remaining(&Player::White, at(40)),
+            Duration::from_secs(52)
+        );
+        assert_eq!(
+            clock.remaining(&Player::Black, at(40)),
+            Duration::from_secs(30)
+        );
+
+        assert!(!clock.is_flagged(&Player::Black, at(69)));
+        assert!(clock.is_flagged(&Player::Black, at(70)));
+        assert_eq!(
+            format_duration(clock.remaining(&Player::White, at(70))),
+            "00:52"
+        );
+
+        // Huge increments saturate instead of overflowing
+        let mut clock = Clock::new(TimeControl::parse("1e17+18446744073709551615").unwrap());
+        clock.start(&Player::White, start);
+        clock.press(at(1));
+        assert_eq!(clock.remaining(&Player::White, at(1)), Duration::MAX);
+    }
+}
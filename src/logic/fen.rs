@@ -0,0 +1,408 @@
+use crate::logic::basic::{Coordinate, Player};
+use crate::logic::board::{Board, TileContent, BOARD_SIZE};
+use crate::logic::castling::{CastlingRights, CastlingSide};
+use crate::logic::pieces::Piece;
+use crate::logic::validation::InvalidPosition;
+
+use std::error::Error;
+use std::fmt;
+
+pub const FEN_DEFAULT: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError {
+    FieldCount(usize),
+    RankCount(usize),
+    RankLength(String),
+    InvalidPiece(char),
+    InvalidTurn(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    InvalidPosition(InvalidPosition),
+}
+
+impl Error for FenError {}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FieldCount(count) => write!(
+                f,
+                "FEN must consist of 6 space-separated fields (or 4 without move counters), found {}",
+                count
+            ),
+            Self::RankCount(count) => write!(
+                f,
+                "Piece placement must describe {} ranks, found {}",
+                BOARD_SIZE, count
+            ),
+            Self::RankLength(rank) => write!(
+                f,
+                "Rank \"{}\" does not describe exactly {} squares",
+                rank, BOARD_SIZE
+            ),
+            Self::InvalidPiece(letter) => write!(f, "'{}' is not a valid piece letter", letter),
+            Self::InvalidTurn(turn) => {
+                write!(f, "Side to move must be \"w\" or \"b\", found \"{}\"", turn)
+            }
+            Self::InvalidCastling(castling) => write!(
+                f,
+                "Castling availability \"{}\" is invalid",
+                castling
+            ),
+            Self::InvalidEnPassant(square) => write!(
+                f,
+                "En passant target \"{}\" is not a valid square for the side to move",
+                square
+            ),
+            Self::InvalidHalfmoveClock(clock) => write!(
+                f,
+                "Halfmove clock must be a non-negative number, found \"{}\"",
+                clock
+            ),
+            Self::InvalidFullmoveNumber(number) => write!(
+                f,
+                "Fullmove number must be a positive number, found \"{}\"",
+                number
+            ),
+            Self::InvalidPosition(error) => write!(f, "Position is invalid: {}", error),
+        }
+    }
+}
+
+impl Board {
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+
+        if fields.len() != 6 && fields.len() != 4 {
+            return Err(FenError::FieldCount(fields.len()));
+        }
+
+        let mut board = Self::from_tiles(parse_placement(fields[0])?);
+        board.turn = parse_turn(fields[1])?;
+
+        board.castling = parse_castling(fields[2])?;
+        board.en_passant = parse_en_passant(&board.turn, fields[3])?;
+
+        if fields.len() == 6 {
+            board.halfmove_clock = fields[4]
+                .parse()
+                .map_err(|_| FenError::InvalidHalfmoveClock(fields[4].to_string()))?;
+            board.fullmove_number = match fields[5].parse() {
+                Ok(number) if number > 0 => number,
+                _ => Err(FenError::InvalidFullmoveNumber(fields[5].to_string()))?,
+            };
+        }
+
+        board.validate().map_err(FenError::InvalidPosition)?;
+
+        Ok(board)
+    }
+
+    pub fn to_fen(&self) -> String {
+        format!(
+            "{} {} {} {} {} {}",
+            format_placement(self),
+            match self.turn {
+                Player::White => "w",
+                Player::Black => "b",
+            },
+            format_castling(self),
+            format_en_passant(self),
+            self.halfmove_clock,
+            self.fullmove_number,
+        )
+    }
+}
+
+fn parse_placement(placement: &str) -> Result<[[TileContent; BOARD_SIZE]; BOARD_SIZE], FenError> {
+    let ranks: Vec<&str> = placement.split('/').collect();
+
+    if ranks.len() != BOARD_SIZE {
+        return Err(FenError::RankCount(ranks.len()));
+    }
+
+    let mut tiles: [[TileContent; BOARD_SIZE]; BOARD_SIZE] = Default::default();
+
+    for (y, rank) in ranks.iter().enumerate() {
+        let mut x = 0;
+
+        for letter in rank.chars() {
+            if let Some(empty) = letter.to_digit(10) {
+                if empty == 0 {
+                    return Err(FenError::RankLength(rank.to_string()));
+                }
+                x += empty as usize;
+            } else {
+                if x >= BOARD_SIZE {
+                    return Err(FenError::RankLength(rank.to_string()));
+                }
+                let piece =
+                    Piece::from_letter(letter).map_err(|_| FenError::InvalidPiece(letter))?;
+                tiles[y][x] = TileContent::Piece(piece);
+                x += 1;
+            }
+        }
+
+        if x != BOARD_SIZE {
+            return Err(FenError::RankLength(rank.to_string()));
+        }
+    }
+
+    Ok(tiles)
+}
+
+fn parse_turn(turn: &str) -> Result<Player, FenError> {
+    match turn {
+        "w" => Ok(Player::White),
+        "b" => Ok(Player::Black),
+        _ => Err(FenError::InvalidTurn(turn.to_string())),
+    }
+}
+
+fn parse_castling(castling: &str) -> Result<CastlingRights, FenError> {
+    let mut rights = CastlingRights::none();
+
+    if castling == "-" {
+        return Ok(rights);
+    }
+
+    let error = || FenError::InvalidCastling(castling.to_string());
+
+    for letter in castling.chars() {
+        let (player, side) = match letter {
+            'K' => (Player::White, CastlingSide::KingSide),
+            'Q' => (Player::White, CastlingSide::QueenSide),
+            'k' => (Player::Black, CastlingSide::KingSide),
+            'q' => (Player::Black, CastlingSide::QueenSide),
+            _ => return Err(error()),
+        };
+
+        if rights.has(&player, &side) {
+            return Err(error());
+        }
+
+        rights.set(&player, &side, true);
+    }
+
+    Ok(rights)
+}
+
+fn parse_en_passant(turn: &Player, square: &str) -> Result<Option<Coordinate>, FenError> {
+    if square == "-" {
+        return Ok(None);
+    }
+
+    let error = || FenError::InvalidEnPassant(square.to_string());
+    let target = Coordinate::try_from_field_name(square).map_err(|_| error())?;
+
+    // The board remembers the pawn that made the double move, not the square it skipped
+    let (target_y, pawn_y) = match turn {
+        Player::White => (2, 3),
+        Player::Black => (BOARD_SIZE - 3, BOARD_SIZE - 4),
+    };
+
+    if target.yv() != target_y {
+        return Err(error());
+    }
+
+    Ok(Some(Coordinate::try_new(target.xv(), pawn_y).unwrap()))
+}
+
+fn format_placement(board: &Board) -> String {
+    let mut ranks: Vec<String> = Vec::with_capacity(BOARD_SIZE);
+
+    for row in board.tiles().iter() {
+        let mut rank = String::new();
+        let mut empty = 0;
+
+        for tile in row.iter() {
+            match tile {
+                TileContent::Empty => empty += 1,
+                TileContent::Piece(piece) => {
+                    if empty > 0 {
+                        rank.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    rank.push(piece.to_letter());
+                }
+            }
+        }
+
+        if empty > 0 {
+            rank.push_str(&empty.to_string());
+        }
+
+        ranks.push(rank);
+    }
+
+    ranks.join("/")
+}
+
+fn format_castling(board: &Board) -> String {
+    let mut castling = String::new();
+
+    for (letter, player, side) in [
+        ('K', Player::White, CastlingSide::KingSide),
+        ('Q', Player::White, CastlingSide::QueenSide),
+        ('k', Player::Black, CastlingSide::KingSide),
+        ('q', Player::Black, CastlingSide::QueenSide),
+    ] {
+        if board.castling.has(&player, &side) {
+            castling.push(letter);
+        }
+    }
+
+    if castling.is_empty() {
+        "-".to_string()
+    } else {
+        castling
+    }
+}
+
+fn format_en_passant(board: &Board) -> String {
+    match board.en_passant_target() {
+        Some(target) => target.to_field_name().to_lowercase(),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::basic::Player;
+    use crate::logic::board::{Board, TileContent};
+    use crate::logic::castling::CastlingSide;
+    use crate::logic::pieces::tests::{c, m};
+    use crate::logic::pieces::{king, pawn, Piece};
+
+    use super::{FenError, FEN_DEFAULT};
+
+    const FEN_KIWIPETE: &str =
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    #[test]
+    fn test_default_position() {
+        let board = Board::from_fen(FEN_DEFAULT).unwrap();
+        let default = Board::default();
+
+        assert_eq!(board.tiles(), default.tiles());
+        assert_eq!(board.turn, Player::White);
+        assert_eq!(default.to_fen(), FEN_DEFAULT);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for fen in [
+            FEN_DEFAULT,
+            FEN_KIWIPETE,
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        ] {
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn test_optional_move_counters() {
+        let board = Board::from_fen("8/8/8/8/8/8/8/K6k b - -").unwrap();
+        assert_eq!(board.turn, Player::Black);
+        assert_eq!(board.halfmove_clock, 0);
+        assert_eq!(board.fullmove_number, 1);
+    }
+
+    #[test]
+    fn test_castling_rights() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1").unwrap();
+
+        assert!(board.castling.has(&Player::White, &CastlingSide::KingSide));
+        assert!(!board.castling.has(&Player::White, &CastlingSide::QueenSide));
+        assert!(!board.castling.has(&Player::Black, &CastlingSide::KingSide));
+        assert!(board.castling.has(&Player::Black, &CastlingSide::QueenSide));
+
+        // King and rooks on their home squares without any castling rights
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+        assert!(!board.castling.has_any(&Player::White));
+        assert!(!board.castling.has_any(&Player::Black));
+        assert_eq!(king::all_moves(&board, &c(4, 7)).len(), 5);
+    }
+
+    #[test]
+    fn test_en_passant() {
+        let board = Board::from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3")
+            .unwrap();
+        assert_eq!(board.en_passant, Some(c(3, 3)));
+
+        let new_board = pawn::move_piece(&board, &m(&board, c(4, 3), c(3, 2))).unwrap();
+        assert!(matches!(new_board.get_tile(&c(3, 3)), TileContent::Empty));
+
+        // Inconsistent squares on unvalidated boards are written as no en passant
+        for en_passant in [c(0, 0), c(7, 7), c(4, 6)] {
+            let mut invalid = Board::default();
+            invalid.en_passant = Some(en_passant);
+            assert_eq!(invalid.to_fen(), FEN_DEFAULT);
+        }
+        for (turn, en_passant) in [(Player::White, c(0, 0)), (Player::Black, c(7, 7))] {
+            let mut invalid = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+            invalid.set_tile(
+                &c(0, 0),
+                TileContent::Piece(Piece::from_letter('p').unwrap()),
+            );
+            invalid.set_tile(
+                &c(7, 7),
+                TileContent::Piece(Piece::from_letter('P').unwrap()),
+            );
+            invalid.turn = turn;
+            invalid.en_passant = Some(en_passant);
+            assert!(invalid.to_fen().ends_with(" - - 0 1"));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w -").err(),
+            Some(FenError::FieldCount(3))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8 w - - 0 1").err(),
+            Some(FenError::RankCount(7))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/7 w - - 0 1").err(),
+            Some(FenError::RankLength("7".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8p w - - 0 1").err(),
+            Some(FenError::RankLength("8p".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/7x w - - 0 1").err(),
+            Some(FenError::InvalidPiece('x'))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").err(),
+            Some(FenError::InvalidTurn("x".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w KK - 0 1").err(),
+            Some(FenError::InvalidCastling("KK".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w - e3 0 1").err(),
+            Some(FenError::InvalidEnPassant("e3".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w - - -1 1").err(),
+            Some(FenError::InvalidHalfmoveClock("-1".to_string()))
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 0").err(),
+            Some(FenError::InvalidFullmoveNumber("0".to_string()))
+        );
+    }
+}
@@ -1,34 +1,171 @@
-use crate::logic::basic::Coordinate;
+use crate::logic::basic::{Coordinate, Player};
 use crate::logic::board::{Board, TileContent};
+use crate::logic::clock::{Clock, TimeControl};
+use crate::logic::pgn::GameInfo;
+use crate::logic::result::{GameResult, Termination};
+use crate::logic::zobrist::ZobristKey;
+
+use std::time::Instant;
 
 use super::pieces::{Move, MoveError, PieceType};
 
 pub enum GameState {
     WaitMove(bool),
     SelectPromotionType(Coordinate, Coordinate),
-    CheckMate,
+    GameOver,
 }
 
 pub struct Game {
     pub board: Board,
     pub state: GameState,
+    pub result: Option<GameResult>,
+    pub draw_offer: Option<Player>,
+    pub clock: Option<Clock>,
+    pub info: GameInfo,
+    initial_board: Board,
+    moves: Vec<Move>,
+    draw_offer_ply: usize,
+    positions: Vec<ZobristKey>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::from_board(Board::default())
+    }
 }
 
 impl Game {
-    pub fn default() -> Self {
-        let board = Board::default();
-        let is_check = board.is_player_on_check(&board.turn);
+    pub fn from_board(board: Board) -> Self {
+        Self::new(board, None)
+    }
 
-        Self {
+    pub fn new(board: Board, time_control: Option<TimeControl>) -> Self {
+        let mut game = Self {
+            initial_board: board.clone(),
             board,
-            state: GameState::WaitMove(is_check),
+            state: GameState::WaitMove(false),
+            result: None,
+            draw_offer: None,
+            clock: None,
+            info: GameInfo::default(),
+            moves: Vec::new(),
+            draw_offer_ply: 0,
+            positions: Vec::new(),
+        };
+        game.record_position();
+
+        if let Some(time_control) = time_control {
+            game.start_clock(time_control);
         }
+        game
+    }
+
+    pub fn start_clock(&mut self, time_control: TimeControl) {
+        // The side to move starts thinking right away
+        let mut clock = Clock::new(time_control);
+        clock.start(&self.board.turn, Instant::now());
+        self.clock = Some(clock);
     }
 
     pub fn reset(&mut self) {
-        self.board = Board::default();
-        let is_check = self.board.is_player_on_check(&self.board.turn);
-        self.state = GameState::WaitMove(is_check);
+        // Start over from the same position with the same time control and players
+        let time_control = self.clock.as_ref().map(|clock| clock.control().clone());
+        let info = GameInfo {
+            date: GameInfo::default().date,
+            ..self.info.clone()
+        };
+
+        *self = Self::new(self.initial_board.clone(), time_control);
+        self.info = info;
+    }
+
+    pub fn initial_board(&self) -> &Board {
+        &self.initial_board
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    pub fn repetition_count(&self) -> usize {
+        let current = self.board.hash();
+        self.positions.iter().filter(|key| **key == current).count()
+    }
+
+    pub fn claimable_draw(&self) -> Option<Termination> {
+        if !matches!(self.state, GameState::WaitMove(_)) {
+            None
+        } else if self.repetition_count() >= 3 {
+            Some(Termination::ThreefoldRepetition)
+        } else if self.board.halfmove_clock >= 100 {
+            Some(Termination::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    pub fn can_claim_draw(&self) -> bool {
+        self.claimable_draw().is_some()
+    }
+
+    pub fn claim_draw(&mut self) -> bool {
+        match self.claimable_draw() {
+            Some(termination) => {
+                self.finish(GameResult::draw(termination));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn offer_draw(&mut self) -> bool {
+        // Only the side to move offers, the offer stays open through the opponent's turn
+        if self.is_over() || self.draw_offer.is_some() {
+            return false;
+        }
+
+        self.draw_offer = Some(self.board.turn.clone());
+        self.draw_offer_ply = self.moves.len();
+        true
+    }
+
+    pub fn accept_draw(&mut self, player: &Player) -> bool {
+        if self.is_over() || !self.can_answer_draw_offer(player) {
+            return false;
+        }
+
+        self.draw_offer = None;
+        self.finish(GameResult::draw(Termination::Agreement));
+        true
+    }
+
+    pub fn decline_draw(&mut self, player: &Player) -> bool {
+        if !self.can_answer_draw_offer(player) {
+            return false;
+        }
+
+        self.draw_offer = None;
+        true
+    }
+
+    fn can_answer_draw_offer(&self, player: &Player) -> bool {
+        // Only the opponent of the offering side can answer
+        self.draw_offer
+            .as_ref()
+            .is_some_and(|offering| offering != player)
+    }
+
+    pub fn resign(&mut self, player: &Player) -> bool {
+        if self.is_over() {
+            return false;
+        }
+
+        self.finish(GameResult::win(&player.other(), Termination::Resignation));
+        true
+    }
+
+    pub fn is_over(&self) -> bool {
+        matches!(self.state, GameState::GameOver)
     }
 
     pub fn can_move_from(&self, coordinate: &Coordinate) -> bool {
@@ -41,12 +178,10 @@ impl Game {
     }
 
     pub fn can_move(&self, from: &Coordinate, to: &Coordinate) -> bool {
-        let tile = self.board.get_tile(from);
-
-        match tile {
-            TileContent::Piece(piece) => piece.can_move(&self.board, from, to),
-            TileContent::Empty => false,
-        }
+        self.board
+            .legal_moves_from(from)
+            .iter()
+            .any(|a_move| a_move.get_to() == to)
     }
 
     pub fn move_piece_with_promotion(
@@ -55,53 +190,390 @@ impl Game {
         to: &Coordinate,
         piece_type: &PieceType,
     ) -> Result<(), MoveError> {
-        let moved = self.try_move(from, &Move::Promotion(to.clone(), piece_type.clone()));
-
-        match moved {
-            Ok(new_board) => {
-                self.board = new_board;
-                Ok(())
-            }
-            Err(err) => Err(err),
-        }
+        let a_move = Move::new(&self.board, from, to, Some(piece_type.clone()))?;
+        self.play_move(&a_move)
     }
 
     pub fn move_piece(&mut self, from: &Coordinate, to: &Coordinate) -> Result<(), MoveError> {
-        let moved = self.try_move(from, &Move::Regular(to.clone()));
+        let a_move = Move::new(&self.board, from, to, None)?;
 
-        match moved {
-            Ok(new_board) => {
-                self.board = new_board;
-                Ok(())
-            }
+        match self.play_move(&a_move) {
             Err(MoveError::PromotionRequired) => {
                 self.state = GameState::SelectPromotionType(from.clone(), to.clone());
                 Ok(())
             }
-            Err(err) => Err(err),
+            moved => moved,
+        }
+    }
+
+    pub fn play_move(&mut self, a_move: &Move) -> Result<(), MoveError> {
+        // A move after the flag fell does not count
+        self.check_time();
+
+        let new_board = self.try_move(a_move)?;
+        self.commit_move(a_move, new_board);
+        Ok(())
+    }
+
+    pub fn check_time(&mut self) -> bool {
+        self.check_time_at(Instant::now())
+    }
+
+    fn check_time_at(&mut self, now: Instant) -> bool {
+        let flagged = match &self.clock {
+            Some(clock) => !self.is_over() && clock.is_flagged(&self.board.turn, now),
+            None => false,
+        };
+
+        if flagged {
+            self.finish(self.timeout_result());
+        }
+
+        flagged
+    }
+
+    fn timeout_result(&self) -> GameResult {
+        // Running out of time only loses if the opponent has anything left to mate with
+        let opponent = self.board.turn.other();
+
+        if self.board.has_only_king(&opponent) {
+            GameResult::draw(Termination::Timeout)
+        } else {
+            GameResult::win(&opponent, Termination::Timeout)
+        }
+    }
+
+    fn try_move(&self, a_move: &Move) -> Result<Board, MoveError> {
+        if self.is_over() {
+            return Err(MoveError::GameOver);
         }
+
+        self.board.apply_move(a_move)
+    }
+
+    fn commit_move(&mut self, a_move: &Move, new_board: Board) {
+        if let Some(clock) = &mut self.clock {
+            clock.press(Instant::now());
+        }
+
+        // The offering side may still make the move it offered with, its next one withdraws
+        if self.draw_offer.as_ref() == Some(&self.board.turn)
+            && self.moves.len() > self.draw_offer_ply
+        {
+            self.draw_offer = None;
+        }
+
+        self.board = new_board;
+        self.moves.push(a_move.clone());
+        self.record_position();
     }
 
-    fn try_move(&mut self, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-        let tile = self.board.get_tile(from);
+    fn record_position(&mut self) {
+        self.positions.push(self.board.hash());
+        self.update_state();
+    }
 
-        let new_board = match tile {
-            TileContent::Piece(piece) => piece.move_piece(&self.board, from, a_move),
-            TileContent::Empty => Err(MoveError::IllegalMove),
-        }?;
+    fn update_state(&mut self) {
+        match self.evaluate_result() {
+            Some(result) => self.finish(result),
+            None => {
+                self.state = GameState::WaitMove(self.board.is_player_on_check(&self.board.turn))
+            }
+        }
+    }
 
-        if new_board.is_player_on_check(&self.board.turn) {
-            Err(MoveError::IsCheck)
+    fn evaluate_result(&self) -> Option<GameResult> {
+        if self.board.is_current_player_checkmate() {
+            Some(GameResult::win(
+                &self.board.turn.other(),
+                Termination::Checkmate,
+            ))
+        } else if self.board.is_current_player_stalemate() {
+            Some(GameResult::draw(Termination::Stalemate))
+        } else if self.board.is_insufficient_material() {
+            Some(GameResult::draw(Termination::InsufficientMaterial))
+        } else if self.repetition_count() >= 5 {
+            Some(GameResult::draw(Termination::FivefoldRepetition))
+        } else if self.board.halfmove_clock >= 150 {
+            Some(GameResult::draw(Termination::SeventyFiveMoveRule))
         } else {
-            let on_check = new_board.is_player_on_check(&new_board.turn);
+            None
+        }
+    }
+
+    pub(crate) fn finish(&mut self, result: GameResult) {
+        if let Some(clock) = &mut self.clock {
+            clock.stop(Instant::now());
+        }
+
+        self.result = Some(result);
+        self.state = GameState::GameOver;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::board::{Board, TileContent};
+    use crate::logic::pieces::tests::c;
+
+    use crate::logic::basic::Player;
+    use crate::logic::clock::TimeControl;
+    use crate::logic::pieces::MoveError;
+    use crate::logic::result::{GameResult, Termination};
+
+    use std::time::Duration;
+
+    use super::{Game, GameState};
+
+    #[test]
+    fn test_checkmate() {
+        let board = Board::from_fen("k7/8/1K6/8/8/8/7Q/8 w - - 0 1").unwrap();
+        let mut game = Game::from_board(board);
+
+        game.move_piece(&c(7, 6), &c(7, 0)).unwrap();
+        assert_eq!(
+            game.result,
+            Some(GameResult::win(&Player::White, Termination::Checkmate))
+        );
+        assert!(game.move_piece(&c(0, 0), &c(1, 0)).is_err());
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = Game::default();
+        game.move_piece(&c(4, 6), &c(4, 4)).unwrap();
+
+        assert!(game.resign(&Player::Black));
+        assert!(game.is_over());
+        assert_eq!(
+            game.result,
+            Some(GameResult::win(&Player::White, Termination::Resignation))
+        );
+        assert!(!game.resign(&Player::White));
+
+        // The final position stays available for review
+        assert!(matches!(
+            game.board.get_tile(&c(4, 4)),
+            TileContent::Piece(_)
+        ));
+
+        game.reset();
+        assert!(!game.is_over());
+        assert_eq!(game.result, None);
+    }
+
+    #[test]
+    fn test_draw_offer() {
+        let mut game = Game::default();
+        assert!(!game.accept_draw(&Player::Black));
+
+        assert!(game.offer_draw());
+        assert_eq!(game.draw_offer, Some(Player::White));
+        assert!(!game.offer_draw());
+
+        // The offering side cannot answer its own offer
+        assert!(!game.accept_draw(&Player::White));
+        assert!(!game.decline_draw(&Player::White));
+        assert!(game.decline_draw(&Player::Black));
+        assert_eq!(game.draw_offer, None);
+
+        // The offer stays open through the opponent's turn
+        assert!(game.offer_draw());
+        game.move_piece(&c(4, 6), &c(4, 4)).unwrap();
+        assert_eq!(game.draw_offer, Some(Player::White));
+        game.move_piece(&c(4, 1), &c(4, 3)).unwrap();
+        assert_eq!(game.draw_offer, Some(Player::White));
 
-            if on_check && new_board.is_current_player_checkmate() {
-                self.state = GameState::CheckMate;
-            } else {
-                self.state = GameState::WaitMove(on_check);
+        // Withdrawn with the offering side's next move
+        game.move_piece(&c(6, 7), &c(5, 5)).unwrap();
+        assert_eq!(game.draw_offer, None);
+        assert!(!game.accept_draw(&Player::Black));
+
+        assert!(game.offer_draw());
+        assert_eq!(game.draw_offer, Some(Player::Black));
+        game.move_piece(&c(1, 0), &c(2, 2)).unwrap();
+        assert!(!game.accept_draw(&Player::Black));
+        assert!(game.accept_draw(&Player::White));
+        assert_eq!(game.result, Some(GameResult::draw(Termination::Agreement)));
+        assert!(!game.offer_draw());
+    }
+
+    #[test]
+    fn test_stalemate() {
+        let board = Board::from_fen("k7/8/8/1Q6/8/8/8/7K w - - 0 1").unwrap();
+        let mut game = Game::from_board(board);
+        assert!(matches!(game.state, GameState::WaitMove(false)));
+
+        game.move_piece(&c(1, 3), &c(1, 2)).unwrap();
+        assert_eq!(game.result, Some(GameResult::draw(Termination::Stalemate)));
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn test_repetition() {
+        let mut game = Game::default();
+        assert_eq!(game.repetition_count(), 1);
+
+        for i in 2..=5 {
+            // Knights out and back again
+            game.move_piece(&c(6, 7), &c(5, 5)).unwrap();
+            game.move_piece(&c(6, 0), &c(5, 2)).unwrap();
+            game.move_piece(&c(5, 5), &c(6, 7)).unwrap();
+            game.move_piece(&c(5, 2), &c(6, 0)).unwrap();
+            assert_eq!(game.repetition_count(), i);
+
+            match i {
+                2 => assert!(!game.can_claim_draw()),
+                3 | 4 => assert!(game.can_claim_draw()),
+                _ => assert_eq!(
+                    game.result,
+                    Some(GameResult::draw(Termination::FivefoldRepetition))
+                ),
             }
+        }
+    }
+
+    #[test]
+    fn test_claim_threefold_repetition() {
+        let mut game = Game::default();
+        assert!(!game.claim_draw());
 
-            Ok(new_board)
+        for _ in 0..2 {
+            game.move_piece(&c(1, 7), &c(2, 5)).unwrap();
+            game.move_piece(&c(1, 0), &c(2, 2)).unwrap();
+            game.move_piece(&c(2, 5), &c(1, 7)).unwrap();
+            game.move_piece(&c(2, 2), &c(1, 0)).unwrap();
         }
+
+        assert!(game.claim_draw());
+        assert_eq!(
+            game.result,
+            Some(GameResult::draw(Termination::ThreefoldRepetition))
+        );
+    }
+
+    #[test]
+    fn test_fifty_move_rule() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 98 80").unwrap();
+        let mut game = Game::from_board(board);
+
+        game.move_piece(&c(0, 7), &c(0, 6)).unwrap();
+        assert!(!game.can_claim_draw());
+        game.move_piece(&c(4, 0), &c(3, 0)).unwrap();
+        assert_eq!(game.claimable_draw(), Some(Termination::FiftyMoveRule));
+        assert!(game.claim_draw());
+        assert_eq!(
+            game.result,
+            Some(GameResult::draw(Termination::FiftyMoveRule))
+        );
+    }
+
+    #[test]
+    fn test_seventy_five_move_rule() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 149 80").unwrap();
+        let mut game = Game::from_board(board);
+        assert_eq!(game.claimable_draw(), Some(Termination::FiftyMoveRule));
+
+        game.move_piece(&c(0, 7), &c(0, 6)).unwrap();
+        assert_eq!(
+            game.result,
+            Some(GameResult::draw(Termination::SeventyFiveMoveRule))
+        );
+
+        // Checkmate on the last move takes precedence
+        let board = Board::from_fen("k7/8/1K6/8/8/8/7Q/8 w - - 149 80").unwrap();
+        let mut game = Game::from_board(board);
+        game.move_piece(&c(7, 6), &c(7, 0)).unwrap();
+        assert_eq!(
+            game.result,
+            Some(GameResult::win(&Player::White, Termination::Checkmate))
+        );
+    }
+
+    #[test]
+    fn test_insufficient_material() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1N2K3 b - - 0 1",
+            "2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+            "4kb2/8/8/8/8/8/1B6/4K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(board.is_insufficient_material(), "{}", fen);
+        }
+
+        for fen in [
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+            "1b2k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+            "1n2k3/8/8/8/8/8/8/1N2K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1",
+            "1n2k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(!board.is_insufficient_material(), "{}", fen);
+        }
+
+        // Capturing the last rook ends the game
+        let board = Board::from_fen("4k3/8/8/8/8/8/1r6/2B1K3 w - - 0 1").unwrap();
+        let mut game = Game::from_board(board);
+        assert!(matches!(game.state, GameState::WaitMove(false)));
+        game.move_piece(&c(2, 7), &c(1, 6)).unwrap();
+        assert_eq!(
+            game.result,
+            Some(GameResult::draw(Termination::InsufficientMaterial))
+        );
+    }
+
+    #[test]
+    fn test_repetition_en_passant() {
+        // After the double move, no black pawn could capture en passant
+        let board = Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").unwrap();
+        let other = Board::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1").unwrap();
+        assert_eq!(board.hash(), other.hash());
+        assert!(board == other);
+
+        let board = Board::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+        let other = Board::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1").unwrap();
+        assert_ne!(board.hash(), other.hash());
+        assert!(board != other);
+    }
+
+    #[test]
+    fn test_game_over_on_load() {
+        let board = Board::from_fen("k7/8/1Q6/8/8/8/8/7K b - - 0 1").unwrap();
+        let game = Game::from_board(board);
+        assert_eq!(game.result, Some(GameResult::draw(Termination::Stalemate)));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let no_time = TimeControl {
+            base: Duration::ZERO,
+            increment: Duration::ZERO,
+        };
+
+        let mut game = Game::new(Board::default(), Some(no_time.clone()));
+        assert_eq!(
+            game.move_piece(&c(4, 6), &c(4, 4)).err(),
+            Some(MoveError::GameOver)
+        );
+        assert_eq!(
+            game.result,
+            Some(GameResult::win(&Player::Black, Termination::Timeout))
+        );
+
+        // A lone king cannot win on time
+        let fen = "4k3/8/8/8/8/8/8/4KQ2 w - - 0 1";
+        let mut game = Game::new(Board::from_fen(fen).unwrap(), Some(no_time));
+        assert!(game.check_time());
+        assert_eq!(game.result, Some(GameResult::draw(Termination::Timeout)));
+
+        // Starting over keeps position and time control
+        game.reset();
+        assert!(!game.is_over());
+        assert_eq!(game.board.to_fen(), fen);
+        assert!(game.clock.is_some());
     }
 }
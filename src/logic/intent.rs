@@ -1,11 +1,10 @@
-use crate::logic::basic::Coordinate;
+use crate::logic::basic::{Coordinate, CoordinateError};
 use crate::logic::board::BOARD_SIZE;
-use crate::utils::ValueError;
 
 use std::str::Chars;
 
 use super::game::GameState;
-use super::pieces::PieceType;
+use super::pieces::{InvalidPieceLetter, PieceType};
 
 pub struct PartialCoordinate {
     x: Option<usize>,
@@ -13,7 +12,7 @@ pub struct PartialCoordinate {
 }
 
 impl PartialCoordinate {
-    pub fn try_new(xo: Option<usize>, yo: Option<usize>) -> Result<Self, ValueError> {
+    pub fn try_new(xo: Option<usize>, yo: Option<usize>) -> Result<Self, CoordinateError> {
         Ok(Self {
             x: xo.map(Coordinate::try_axis_bound).transpose()?,
             y: yo.map(Coordinate::try_axis_bound).transpose()?,
@@ -39,8 +38,16 @@ impl PartialCoordinate {
 
 pub enum Intent {
     Move(Option<PartialCoordinate>, Option<PartialCoordinate>),
+    Notation(String),
     SelectPromotionType(PieceType),
     Surrender,
+    ClaimDraw,
+    OfferDraw,
+    AcceptDraw,
+    DeclineDraw,
+    NewGame,
+    Export,
+    SavePgn,
     Invalid,
     None,
 }
@@ -55,10 +62,25 @@ impl Intent {
                     Err(_) => (),
                 };
 
-                match Self::parse_surrender(cmd) {
-                    Some(intent) => return intent,
-                    None => (),
-                };
+                if let Some(intent) = Self::parse_surrender(cmd) {
+                    return intent;
+                }
+
+                if let Some(intent) = Self::parse_claim_draw(cmd) {
+                    return intent;
+                }
+
+                if let Some(intent) = Self::parse_draw_offer(cmd) {
+                    return intent;
+                }
+
+                if let Some(intent) = Self::parse_save_pgn(cmd) {
+                    return intent;
+                }
+
+                if let Some(intent) = Self::parse_notation(cmd) {
+                    return intent;
+                }
             }
             GameState::SelectPromotionType(..) => {
                 match Self::try_parse_select_promotion_type(cmd) {
@@ -67,8 +89,14 @@ impl Intent {
                     Err(_) => (),
                 };
             }
-            GameState::CheckMate => {
-                () // TODO
+            GameState::GameOver => {
+                if let Some(intent) = Self::parse_game_over(cmd) {
+                    return intent;
+                }
+
+                if let Some(intent) = Self::parse_save_pgn(cmd) {
+                    return intent;
+                }
             }
         }
 
@@ -78,7 +106,7 @@ impl Intent {
         }
     }
 
-    fn try_parse_select_promotion_type(cmd: &str) -> Result<Option<Self>, ValueError> {
+    fn try_parse_select_promotion_type(cmd: &str) -> Result<Option<Self>, InvalidPieceLetter> {
         let mut chars = cmd.chars();
 
         Ok(match chars.next() {
@@ -87,7 +115,7 @@ impl Intent {
         })
     }
 
-    fn try_parse_move(cmd: &str) -> Result<Option<Self>, ValueError> {
+    fn try_parse_move(cmd: &str) -> Result<Option<Self>, CoordinateError> {
         let mut chars = cmd.chars();
         let first_coord = coordinate_from_chars(&mut chars)?;
 
@@ -97,7 +125,7 @@ impl Intent {
 
                 match chars.next() {
                     None => Ok(Some(Self::Move(Some(first), second_coord))),
-                    _ => Err(ValueError),
+                    _ => Err(CoordinateError::InvalidFieldName(cmd.to_string())),
                 }
             }
             None => Ok(None),
@@ -112,9 +140,59 @@ impl Intent {
             false => None,
         }
     }
+
+    fn parse_claim_draw(cmd: &str) -> Option<Self> {
+        let mut chars = cmd.chars();
+
+        match prefixes_from_chars(&mut chars, "claim") {
+            true => Some(Self::ClaimDraw),
+            false => None,
+        }
+    }
+
+    fn parse_draw_offer(cmd: &str) -> Option<Self> {
+        if prefixes_from_chars(&mut cmd.chars(), "draw") {
+            Some(Self::OfferDraw)
+        } else if prefixes_from_chars(&mut cmd.chars(), "accept") {
+            Some(Self::AcceptDraw)
+        } else if prefixes_from_chars(&mut cmd.chars(), "decline") {
+            Some(Self::DeclineDraw)
+        } else {
+            None
+        }
+    }
+
+    fn parse_save_pgn(cmd: &str) -> Option<Self> {
+        match prefixes_from_chars(&mut cmd.chars(), "pgn") {
+            true => Some(Self::SavePgn),
+            false => None,
+        }
+    }
+
+    fn parse_notation(cmd: &str) -> Option<Self> {
+        // SAN and lowercase coordinates are resolved against the board once entered
+        let is_notation = cmd
+            .chars()
+            .all(|c| "abcdefgh12345678KQRBNqrnxO0-=+#".contains(c));
+
+        match is_notation && !cmd.is_empty() {
+            true => Some(Self::Notation(cmd.to_string())),
+            false => None,
+        }
+    }
+
+    fn parse_game_over(cmd: &str) -> Option<Self> {
+        if prefixes_from_chars(&mut cmd.chars(), "new") {
+            Some(Self::NewGame)
+        } else if prefixes_from_chars(&mut cmd.chars(), "export") {
+            Some(Self::Export)
+        } else {
+            None
+        }
+    }
 }
 
-fn coordinate_from_chars(chars: &mut Chars) -> Result<Option<PartialCoordinate>, ValueError> {
+fn coordinate_from_chars(chars: &mut Chars) -> Result<Option<PartialCoordinate>, CoordinateError> {
     let column = match chars.next() {
         Some(c) => Some(char_to_column(c)?),
         None => None,
@@ -142,16 +220,55 @@ fn prefixes_from_chars(chars: &mut Chars, target_str: &str) -> bool {
     true
 }
 
-fn char_to_column(letter: char) -> Result<usize, ValueError> {
+fn char_to_column(letter: char) -> Result<usize, CoordinateError> {
     match letter {
         c @ 'A'..='H' => Ok(c as usize - 'A' as usize),
-        _ => Err(ValueError),
+        _ => Err(CoordinateError::InvalidColumn(letter)),
     }
 }
 
-fn char_to_row(letter: char) -> Result<usize, ValueError> {
+fn char_to_row(letter: char) -> Result<usize, CoordinateError> {
     match letter {
         c @ '1'..='8' => Ok(BOARD_SIZE - 1 - (c as usize - '1' as usize)),
-        _ => Err(ValueError),
+        _ => Err(CoordinateError::InvalidRow(letter)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::game::GameState;
+
+    use super::Intent;
+
+    #[test]
+    fn test_notation_intent() {
+        let state = GameState::WaitMove(false);
+        let notation = |cmd: &str| match Intent::from_partial_command(&state, cmd) {
+            Intent::Notation(text) => Some(text),
+            _ => None,
+        };
+
+        assert_eq!(notation("Nf3"), Some("Nf3".to_string()));
+        assert_eq!(notation("exd5"), Some("exd5".to_string()));
+        assert_eq!(notation("O-O"), Some("O-O".to_string()));
+        assert_eq!(notation("e7e8q"), Some("e7e8q".to_string()));
+
+        // Coordinates, commands and anything else keep their meaning
+        assert!(matches!(
+            Intent::from_partial_command(&state, "E2E4"),
+            Intent::Move(Some(_), Some(_))
+        ));
+        assert!(matches!(
+            Intent::from_partial_command(&state, "draw"),
+            Intent::OfferDraw
+        ));
+        assert!(matches!(
+            Intent::from_partial_command(&state, "pgn"),
+            Intent::SavePgn
+        ));
+        assert!(matches!(
+            Intent::from_partial_command(&state, "hello"),
+            Intent::Invalid
+        ));
     }
 }
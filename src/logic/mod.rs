@@ -1,5 +1,17 @@
 pub mod basic;
+pub(crate) mod bitboard;
 pub mod board;
+pub mod castling;
+pub mod clock;
+pub mod fen;
 pub mod game;
 pub mod intent;
+pub mod opponent;
+pub mod perft;
+pub mod pgn;
 pub mod pieces;
+pub mod result;
+pub mod san;
+pub mod uci;
+pub mod validation;
+pub(crate) mod zobrist;
@@ -0,0 +1,82 @@
+use crate::logic::board::Board;
+use crate::logic::pieces::Move;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone)]
+pub enum Opponent {
+    Human,
+    Random(RandomMover),
+}
+
+impl Opponent {
+    pub fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        // Humans enter their moves at the prompt
+        match self {
+            Self::Human => None,
+            Self::Random(mover) => mover.choose_move(board),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RandomMover {
+    state: u64,
+}
+
+impl RandomMover {
+    pub fn new(seed: u64) -> Self {
+        // Xorshift never leaves a zero state
+        Self { state: seed | 1 }
+    }
+
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        Self::new(nanos)
+    }
+
+    pub fn choose_move(&mut self, board: &Board) -> Option<Move> {
+        let mut moves = board.legal_moves();
+
+        if moves.is_empty() {
+            return None;
+        }
+
+        let index = (self.next() % moves.len() as u64) as usize;
+        Some(moves.swap_remove(index))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::board::Board;
+
+    use super::{Opponent, RandomMover};
+
+    #[test]
+    fn test_random_mover() {
+        let board = Board::default();
+        let legal_moves = board.legal_moves();
+
+        let mut mover = RandomMover::new(42);
+        let mut same_seed = RandomMover::new(42);
+        for _ in 0..10 {
+            let a_move = mover.choose_move(&board).unwrap();
+            assert!(legal_moves.contains(&a_move));
+            assert_eq!(same_seed.choose_move(&board), Some(a_move));
+        }
+
+        let checkmate = Board::from_fen("k6R/8/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+        assert_eq!(mover.choose_move(&checkmate), None);
+        assert_eq!(Opponent::Human.choose_move(&board), None);
+    }
+}
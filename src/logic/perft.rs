@@ -0,0 +1,117 @@
+use crate::logic::board::Board;
+use crate::logic::pieces::Move;
+
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    let mut scratch = board.clone();
+    perft_in_place(&mut scratch, depth)
+}
+
+pub fn divide(board: &Board, depth: u32) -> Vec<(Move, u64)> {
+    // Node count below every root move, to track down differences to a reference engine
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    let mut scratch = board.clone();
+
+    board
+        .legal_moves()
+        .into_iter()
+        .map(|a_move| {
+            let undo = scratch.make_move(&a_move);
+            let nodes = perft_in_place(&mut scratch, depth - 1);
+            scratch.unmake_move(&a_move, undo);
+            (a_move, nodes)
+        })
+        .collect()
+}
+
+fn perft_in_place(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let moves = board.legal_moves();
+
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    moves
+        .iter()
+        .map(|a_move| {
+            let undo = board.make_move(a_move);
+            let nodes = perft_in_place(board, depth - 1);
+            board.unmake_move(a_move, undo);
+            nodes
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::board::Board;
+    use crate::logic::fen::FEN_DEFAULT;
+
+    use super::{divide, perft};
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+    const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+    const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+    fn assert_perft(fen: &str, expected: &[u64]) {
+        let board = Board::from_fen(fen).unwrap();
+
+        for (depth, nodes) in expected.iter().enumerate() {
+            assert_eq!(
+                perft(&board, depth as u32 + 1),
+                *nodes,
+                "{} at depth {}",
+                fen,
+                depth + 1
+            );
+        }
+    }
+
+    #[test]
+    fn test_perft_start_position() {
+        assert_perft(FEN_DEFAULT, &[20, 400, 8902]);
+    }
+
+    #[test]
+    fn test_perft_kiwipete() {
+        assert_perft(KIWIPETE, &[48, 2039]);
+    }
+
+    #[test]
+    fn test_perft_position_3() {
+        assert_perft(POSITION_3, &[14, 191, 2812]);
+    }
+
+    #[test]
+    fn test_perft_position_4() {
+        assert_perft(POSITION_4, &[6, 264, 9467]);
+    }
+
+    #[test]
+    fn test_perft_position_5() {
+        assert_perft(POSITION_5, &[44, 1486, 62379]);
+    }
+
+    #[test]
+    fn test_divide() {
+        let board = Board::from_fen(KIWIPETE).unwrap();
+        let moves = divide(&board, 2);
+
+        assert_eq!(moves.len(), 48);
+        assert_eq!(moves.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+
+        let castling = moves
+            .iter()
+            .find(|(a_move, _)| a_move.to_string() == "E1G1");
+        assert_eq!(castling.map(|(_, nodes)| *nodes), Some(43));
+        assert_eq!(perft(&board, 0), 1);
+        assert!(divide(&board, 0).is_empty());
+    }
+}
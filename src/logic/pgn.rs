@@ -0,0 +1,856 @@
+use crate::logic::basic::Player;
+use crate::logic::board::Board;
+use crate::logic::fen::{FenError, FEN_DEFAULT};
+use crate::logic::game::Game;
+use crate::logic::pieces::MoveError;
+use crate::logic::result::{GameResult, Outcome, Termination};
+use crate::logic::san::SanError;
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Export format lines should stay below 80 characters
+const MAX_LINE_LENGTH: usize = 79;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameInfo {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+}
+
+impl Default for GameInfo {
+    fn default() -> Self {
+        Self {
+            event: "Casual game".to_string(),
+            site: "?".to_string(),
+            date: format_date(SystemTime::now()),
+            round: "-".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PgnError {
+    InvalidTag(usize, usize),
+    UnterminatedComment(usize, usize),
+    UnbalancedVariation(usize, usize),
+    UnexpectedCharacter(usize, usize, char),
+    InvalidFen(usize, FenError),
+    InvalidMove(usize, usize, String, SanError),
+    RejectedMove(usize, usize, String, MoveError),
+}
+
+impl Error for PgnError {}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidTag(game, line) => {
+                write!(
+                    f,
+                    "Game {}, line {}: Tag is not of the form [Name \"value\"]",
+                    game, line
+                )
+            }
+            Self::UnterminatedComment(game, line) => {
+                write!(f, "Game {}, line {}: Comment is never closed", game, line)
+            }
+            Self::UnbalancedVariation(game, line) => write!(
+                f,
+                "Game {}, line {}: Variation parentheses do not match",
+                game, line
+            ),
+            Self::UnexpectedCharacter(game, line, c) => {
+                write!(f, "Game {}, line {}: Unexpected \"{}\"", game, line, c)
+            }
+            Self::InvalidFen(game, err) => write!(f, "Game {}: {}", game, err),
+            Self::InvalidMove(game, ply, san, err) => {
+                write!(f, "Game {}, ply {} ({}): {}", game, ply, san, err)
+            }
+            Self::RejectedMove(game, ply, san, err) => {
+                write!(f, "Game {}, ply {} ({}): {}", game, ply, san, err)
+            }
+        }
+    }
+}
+
+// A game as written in the file, before its moves are checked
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct PgnGame {
+    pub number: usize,
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<String>,
+    pub comment: Option<String>,
+    pub result: Option<String>,
+}
+
+impl PgnGame {
+    fn new(number: usize) -> Self {
+        Self {
+            number,
+            ..Self::default()
+        }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn replay(&self) -> Result<Game, PgnError> {
+        let board = match self.tag("FEN") {
+            Some(fen) => {
+                Board::from_fen(fen).map_err(|err| PgnError::InvalidFen(self.number, err))?
+            }
+            None => Board::default(),
+        };
+
+        let mut game = Game::from_board(board);
+        game.info = self.info();
+
+        for (index, san) in self.moves.iter().enumerate() {
+            let ply = index + 1;
+            let a_move = game
+                .board
+                .parse_san(san)
+                .map_err(|err| PgnError::InvalidMove(self.number, ply, san.clone(), err))?;
+            game.play_move(&a_move)
+                .map_err(|err| PgnError::RejectedMove(self.number, ply, san.clone(), err))?;
+        }
+
+        if game.result.is_none() {
+            if let Some(result) = self.recorded_result() {
+                game.finish(result);
+            }
+        }
+
+        Ok(game)
+    }
+
+    fn info(&self) -> GameInfo {
+        let tag = |name, default: &str| self.tag(name).unwrap_or(default).to_string();
+
+        GameInfo {
+            event: tag("Event", "?"),
+            site: tag("Site", "?"),
+            date: tag("Date", "????.??.??"),
+            round: tag("Round", "?"),
+            white: tag("White", "?"),
+            black: tag("Black", "?"),
+        }
+    }
+
+    fn recorded_result(&self) -> Option<GameResult> {
+        // Results the moves do not explain keep the reason given by the file, if any
+        let token = self.result.as_deref().or(self.tag("Result"))?;
+
+        let outcome = match token {
+            "1-0" => Outcome::WhiteWins,
+            "0-1" => Outcome::BlackWins,
+            "1/2-1/2" => Outcome::Draw,
+            _ => return None,
+        };
+
+        let termination = match self.tag("Termination") {
+            Some(tag) if tag.eq_ignore_ascii_case("time forfeit") => Termination::Timeout,
+            tag => self
+                .commented_termination(&outcome)
+                .unwrap_or_else(|| Termination::Unknown(recorded_reason(tag))),
+        };
+
+        Some(GameResult {
+            outcome,
+            termination,
+        })
+    }
+
+    fn commented_termination(&self, outcome: &Outcome) -> Option<Termination> {
+        // The comment written by Game::to_pgn, e.g. {White wins by resignation}
+        let comment = self.comment.as_deref()?;
+        let label = match outcome.winner() {
+            Some(winner) => comment.strip_prefix(&format!("{} wins by ", winner.to_label()))?,
+            None => comment.strip_prefix("Draw by ")?,
+        };
+
+        // Endings the moves would have shown, like checkmate, are not taken from comments
+        Termination::from_label(label).filter(|termination| {
+            matches!(
+                termination,
+                Termination::Resignation
+                    | Termination::Agreement
+                    | Termination::ThreefoldRepetition
+                    | Termination::FiftyMoveRule
+            )
+        })
+    }
+}
+
+fn recorded_reason(tag: Option<&str>) -> Option<String> {
+    // "normal" only says that the game ended by its result
+    tag.filter(|tag| !tag.eq_ignore_ascii_case("normal"))
+        .map(str::to_string)
+}
+
+pub fn read_pgn(text: &str) -> Result<Vec<Game>, PgnError> {
+    parse_pgn(text)?.iter().map(PgnGame::replay).collect()
+}
+
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut reader = PgnReader::new(text);
+    let mut games = Vec::new();
+    let mut game = PgnGame::new(1);
+    let mut variation_depth = 0;
+
+    while let Some(c) = reader.peek() {
+        let line = reader.line;
+
+        match c {
+            '[' if variation_depth == 0 => {
+                // Tags after moves belong to the next game, even without a result token
+                if !game.moves.is_empty() {
+                    let next = PgnGame::new(game.number + 1);
+                    games.push(std::mem::replace(&mut game, next));
+                }
+                let tag = reader
+                    .read_tag()
+                    .ok_or(PgnError::InvalidTag(game.number, line))?;
+                game.tags.push(tag);
+            }
+            '{' => {
+                let comment = reader
+                    .read_comment()
+                    .ok_or(PgnError::UnterminatedComment(game.number, line))?;
+                if variation_depth == 0 {
+                    game.comment = Some(comment);
+                }
+            }
+            ';' => {
+                reader.skip_past('\n');
+            }
+            '%' if reader.at_line_start => {
+                reader.skip_past('\n');
+            }
+            '(' => {
+                reader.next();
+                variation_depth += 1;
+            }
+            ')' if variation_depth == 0 => {
+                return Err(PgnError::UnbalancedVariation(game.number, line))
+            }
+            ')' => {
+                reader.next();
+                variation_depth -= 1;
+            }
+            c if c.is_whitespace() => {
+                reader.next();
+            }
+            _ => {
+                let token = reader.read_token();
+
+                // Stray brackets and braces end a token without being part of one
+                if token.is_empty() {
+                    return Err(PgnError::UnexpectedCharacter(game.number, line, c));
+                }
+
+                // Moves inside variations and NAGs like $1 are not part of the game
+                if variation_depth > 0 || token.starts_with('$') {
+                    continue;
+                }
+
+                match token.as_str() {
+                    "1-0" | "0-1" | "1/2-1/2" | "*" => {
+                        game.result = Some(token);
+                        let next = PgnGame::new(game.number + 1);
+                        games.push(std::mem::replace(&mut game, next));
+                    }
+                    token => {
+                        let san = strip_move_number(token);
+                        if !san.is_empty() {
+                            game.moves.push(san.to_string());
+                            game.comment = None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if variation_depth > 0 {
+        return Err(PgnError::UnbalancedVariation(game.number, reader.line));
+    }
+    if !game.tags.is_empty() || !game.moves.is_empty() {
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+fn strip_move_number(token: &str) -> &str {
+    // Move numbers may be written without a space, as in 12.e4 or 12...e5
+    match token.rfind('.') {
+        Some(index)
+            if token[..index]
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            &token[index + 1..]
+        }
+        _ => token,
+    }
+}
+
+struct PgnReader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    at_line_start: bool,
+}
+
+impl<'a> PgnReader<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            at_line_start: true,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        self.at_line_start = c == Some('\n');
+        if self.at_line_start {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_past(&mut self, end: char) -> bool {
+        while let Some(c) = self.next() {
+            if c == end {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn read_comment(&mut self) -> Option<String> {
+        // {text}, which cannot contain a closing brace
+        self.next();
+        let mut comment = String::new();
+
+        loop {
+            match self.next()? {
+                '}' => return Some(comment.trim().to_string()),
+                c => comment.push(c),
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn read_token(&mut self) -> String {
+        let mut token = String::new();
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || "[]{}();".contains(c) {
+                break;
+            }
+            token.push(c);
+            self.next();
+        }
+
+        token
+    }
+
+    fn read_tag(&mut self) -> Option<(String, String)> {
+        // [Name "value"] where the value escapes quotes and backslashes
+        self.next();
+        self.skip_whitespace();
+        let name = self.read_token();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return None;
+        }
+
+        self.skip_whitespace();
+        if self.next() != Some('"') {
+            return None;
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.next()? {
+                '"' => break,
+                '\\' => value.push(self.next()?),
+                '\n' => return None,
+                c => value.push(c),
+            }
+        }
+
+        self.skip_whitespace();
+        match self.next() {
+            Some(']') => Some((name, value)),
+            _ => None,
+        }
+    }
+}
+
+impl Game {
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+
+        for (name, value) in self.pgn_tags() {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape_tag_value(&value)));
+        }
+        pgn.push('\n');
+
+        for line in wrap(&self.pgn_movetext()) {
+            pgn.push_str(&line);
+            pgn.push('\n');
+        }
+
+        pgn
+    }
+
+    fn pgn_tags(&self) -> Vec<(&str, String)> {
+        // The Seven Tag Roster comes first and in this order
+        let mut tags = vec![
+            ("Event", self.info.event.clone()),
+            ("Site", self.info.site.clone()),
+            ("Date", self.info.date.clone()),
+            ("Round", self.info.round.clone()),
+            ("White", self.info.white.clone()),
+            ("Black", self.info.black.clone()),
+            ("Result", self.result_token().to_string()),
+        ];
+
+        let fen = self.initial_board().to_fen();
+        if fen != FEN_DEFAULT {
+            tags.push(("SetUp", "1".to_string()));
+            tags.push(("FEN", fen));
+        }
+
+        let termination = match &self.result {
+            Some(result) => match &result.termination {
+                Termination::Timeout => "time forfeit",
+                Termination::Unknown(Some(reason)) => reason,
+                _ => "normal",
+            },
+            None => "unterminated",
+        };
+        tags.push(("Termination", termination.to_string()));
+
+        tags
+    }
+
+    fn pgn_movetext(&self) -> Vec<String> {
+        let initial_board = self.initial_board();
+        let mut number = initial_board.fullmove_number;
+        let mut player = initial_board.turn.clone();
+        let mut tokens = Vec::new();
+
+        for (index, san) in initial_board
+            .to_san_line(self.moves())
+            .into_iter()
+            .enumerate()
+        {
+            match player {
+                Player::White => tokens.push(format!("{}.", number)),
+                // A game starting with Black's move continues the number with an ellipsis
+                Player::Black if index == 0 => tokens.push(format!("{}...", number)),
+                Player::Black => (),
+            }
+            tokens.push(san);
+
+            if player == Player::Black {
+                number += 1;
+            }
+            player = player.other();
+        }
+
+        // Without a known reason the Termination tag says all there is
+        if let Some(result) = self
+            .result
+            .as_ref()
+            .filter(|result| !matches!(result.termination, Termination::Unknown(_)))
+        {
+            tokens.push(format!("{{{}}}", termination_comment(result)));
+        }
+        tokens.push(self.result_token().to_string());
+
+        tokens
+    }
+
+    fn result_token(&self) -> &str {
+        match &self.result {
+            Some(result) => result.outcome.to_token(),
+            None => "*",
+        }
+    }
+}
+
+fn termination_comment(result: &GameResult) -> String {
+    let termination = result.termination.to_label();
+
+    match result.winner() {
+        Some(winner) => format!("{} wins by {}", winner.to_label(), termination),
+        None => format!("Draw by {}", termination),
+    }
+}
+
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn wrap(tokens: &[String]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(token);
+    }
+    lines.push(line);
+
+    lines
+}
+
+fn format_date(time: SystemTime) -> String {
+    let days = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() / 86_400) as i64,
+        Err(_) => return "????.??.??".to_string(),
+    };
+
+    // Converts days since 1970-01-01 to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::basic::Player;
+    use crate::logic::board::Board;
+    use crate::logic::game::Game;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::logic::pieces::MoveError;
+    use crate::logic::result::{GameResult, Termination};
+    use crate::logic::san::SanError;
+
+    use super::{format_date, parse_pgn, read_pgn, wrap, GameInfo, PgnError};
+
+    const GAMES: &str = r#"% Exported by hand
+[Event "Casual game"]
+[Site "London"]
+[Date "1851.06.21"]
+[Round "-"]
+[White "Anderssen, Adolf"]
+[Black "Kieseritzky, Lionel"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 exf4 3. Bc4 Qh4+ 4. Kf1 b5?! {The bishop is attacked.}
+5. Bxb5 (5. Bd5 Nf6 (5... c6 6. Bb3) 6. Nc3) Nf6 $6 ; a comment to the end
+6.Nf3 Qh6 1-0
+
+[Event "Study"]
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 30"]
+[Result "1/2-1/2"]
+
+30... Kd7 31. e4 Kc6 1/2-1/2
+
+[White "Unfinished"]
+
+1. d4 d5 2. c4
+"#;
+
+    #[test]
+    fn test_parse_pgn() {
+        let games = parse_pgn(GAMES).unwrap();
+        assert_eq!(games.len(), 3);
+
+        assert_eq!(games[0].number, 1);
+        assert_eq!(games[0].tag("White"), Some("Anderssen, Adolf"));
+        assert_eq!(
+            games[0].moves,
+            ["e4", "e5", "f4", "exf4", "Bc4", "Qh4+", "Kf1", "b5?!", "Bxb5", "Nf6", "Nf3", "Qh6"]
+        );
+        assert_eq!(games[0].result.as_deref(), Some("1-0"));
+
+        assert_eq!(games[1].moves, ["Kd7", "e4", "Kc6"]);
+        assert_eq!(games[2].number, 3);
+        assert_eq!(games[2].moves, ["d4", "d5", "c4"]);
+        assert_eq!(games[2].result, None);
+
+        let escaped = parse_pgn("[White \"Kieseritzky \\\"Lionel\\\"\"]\n*").unwrap();
+        assert_eq!(escaped[0].tag("White"), Some("Kieseritzky \"Lionel\""));
+    }
+
+    #[test]
+    fn test_read_pgn() {
+        let games = read_pgn(GAMES).unwrap();
+
+        assert_eq!(games[0].moves().len(), 12);
+        assert_eq!(games[0].info.site, "London");
+        assert_eq!(
+            games[0].result,
+            Some(GameResult::win(&Player::White, Termination::Unknown(None)))
+        );
+        assert_eq!(
+            games[0].board.to_fen(),
+            "rnb1kb1r/p1pp1ppp/5n1q/1B6/4Pp2/5N2/PPPP2PP/RNBQ1K1R w kq - 3 7"
+        );
+
+        assert_eq!(
+            games[1].result,
+            Some(GameResult::draw(Termination::Unknown(None)))
+        );
+        assert_eq!(games[1].board.fullmove_number, 32);
+        assert_eq!(games[2].result, None);
+        assert_eq!(games[2].info.white, "Unfinished");
+    }
+
+    #[test]
+    fn test_pgn_round_trip() {
+        let mut game = Game::default();
+        game.info = info();
+        play(&mut game, &["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "O-O"]);
+        game.resign(&Player::Black);
+
+        let games = read_pgn(&game.to_pgn()).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].moves(), game.moves());
+        assert_eq!(games[0].info, game.info);
+        assert_eq!(games[0].result, game.result);
+        assert_eq!(games[0].to_pgn(), game.to_pgn());
+    }
+
+    #[test]
+    fn test_pgn_round_trip_adjudication() {
+        let adjudicated = "[White \"Engine\"]\n[Result \"1-0\"]\n[Termination \"adjudication\"]\n\n1. e4 e5 1-0\n";
+        let game = &read_pgn(adjudicated).unwrap()[0];
+        assert_eq!(
+            game.result,
+            Some(GameResult::win(
+                &Player::White,
+                Termination::Unknown(Some("adjudication".to_string()))
+            ))
+        );
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[Termination \"adjudication\"]"));
+        assert!(pgn.ends_with("1. e4 e5 1-0\n"));
+
+        let games = read_pgn(&pgn).unwrap();
+        assert_eq!(games[0].result, game.result);
+        assert_eq!(games[0].to_pgn(), pgn);
+
+        // Reasons from comments are kept unless the moves contradict them
+        let agreed = read_pgn("1. e4 e5 {Draw by agreement} 1/2-1/2").unwrap();
+        assert_eq!(
+            agreed[0].result,
+            Some(GameResult::draw(Termination::Agreement))
+        );
+        let claimed = read_pgn("1. e4 e5 {White wins by checkmate} 1-0").unwrap();
+        assert_eq!(
+            claimed[0].result,
+            Some(GameResult::win(&Player::White, Termination::Unknown(None)))
+        );
+    }
+
+    #[test]
+    fn test_pgn_errors() {
+        let illegal = "[Event \"First\"]\n1. e4 e5 *\n\n[Event \"Second\"]\n1. e4 e5 2. Ke3 *";
+        assert_eq!(
+            read_pgn(illegal).err(),
+            Some(PgnError::InvalidMove(
+                2,
+                3,
+                "Ke3".to_string(),
+                SanError::IllegalMove("Ke3".to_string())
+            ))
+        );
+        assert_eq!(
+            read_pgn(illegal).err().unwrap().to_string(),
+            "Game 2, ply 3 (Ke3): Ke3 is not a legal move"
+        );
+
+        assert_eq!(
+            parse_pgn("1. e4 e5 *\n[Event First]").err(),
+            Some(PgnError::InvalidTag(2, 2))
+        );
+        assert_eq!(
+            parse_pgn("1. e4 {never closed\n e5 *").err(),
+            Some(PgnError::UnterminatedComment(1, 1))
+        );
+        assert_eq!(
+            parse_pgn("1. e4 (1. d4 d5 2. c4 *").err(),
+            Some(PgnError::UnbalancedVariation(1, 1))
+        );
+        assert_eq!(
+            parse_pgn("1. e4 e5) *").err(),
+            Some(PgnError::UnbalancedVariation(1, 1))
+        );
+        for (text, c) in [
+            ("1. e4 } e5 *", '}'),
+            ("1. e4 ] e5 *", ']'),
+            ("1. e4 e5 ( 2. Nf3 [%clk 0:01] ) *", '['),
+        ] {
+            assert_eq!(
+                parse_pgn(text).err(),
+                Some(PgnError::UnexpectedCharacter(1, 1, c))
+            );
+        }
+        assert_eq!(
+            parse_pgn("[Event \"First\"]\n1. e4 e5 *\n\n1. d4 }")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Game 2, line 4: Unexpected \"}\""
+        );
+        assert!(matches!(
+            read_pgn("[FEN \"8/8/8 w - - 0 1\"]\n*"),
+            Err(PgnError::InvalidFen(1, _))
+        ));
+        assert!(matches!(
+            read_pgn("1. f3 e5 2. g4 Qh4# 3. a3 0-1"),
+            Err(PgnError::InvalidMove(1, 5, _, SanError::IllegalMove(_)))
+        ));
+
+        // Capturing the last pawn ends the game by insufficient material
+        let drawn = "[FEN \"4k3/8/8/8/8/8/3p4/4K3 w - - 0 1\"]\n1. Kxd2 Ke7 *";
+        assert_eq!(
+            read_pgn(drawn).err(),
+            Some(PgnError::RejectedMove(
+                1,
+                2,
+                "Ke7".to_string(),
+                MoveError::GameOver
+            ))
+        );
+    }
+
+    fn play(game: &mut Game, moves: &[&str]) {
+        for text in moves {
+            let a_move = game.board.parse_san(text).unwrap();
+            game.play_move(&a_move).unwrap();
+        }
+    }
+
+    fn info() -> GameInfo {
+        GameInfo {
+            white: "Anderssen, Adolf".to_string(),
+            black: "Kieseritzky \"Lionel\"".to_string(),
+            date: "1851.06.21".to_string(),
+            ..GameInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_export_finished_game() {
+        let mut game = Game::default();
+        game.info = info();
+        play(&mut game, &["f3", "e5", "g4", "Qh4#"]);
+
+        assert_eq!(
+            game.to_pgn(),
+            "[Event \"Casual game\"]\n\
+             [Site \"?\"]\n\
+             [Date \"1851.06.21\"]\n\
+             [Round \"-\"]\n\
+             [White \"Anderssen, Adolf\"]\n\
+             [Black \"Kieseritzky \\\"Lionel\\\"\"]\n\
+             [Result \"0-1\"]\n\
+             [Termination \"normal\"]\n\
+             \n\
+             1. f3 e5 2. g4 Qh4# {Black wins by checkmate} 0-1\n"
+        );
+    }
+
+    #[test]
+    fn test_export_from_position() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 30").unwrap();
+        let mut game = Game::from_board(board);
+        game.info = info();
+        play(&mut game, &["Kd7", "e4", "Kc6"]);
+
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[Result \"*\"]\n"));
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 30\"]\n"));
+        assert!(pgn.contains("[Termination \"unterminated\"]\n"));
+        assert!(pgn.ends_with("\n30... Kd7 31. e4 Kc6 *\n"));
+
+        game.resign(&Player::White);
+        assert!(pgn_movetext(&game).ends_with("Kc6 {Black wins by resignation} 0-1"));
+    }
+
+    #[test]
+    fn test_wrap() {
+        let tokens: Vec<String> = (1..=40).map(|number| format!("{}. Nf3", number)).collect();
+        let lines = wrap(&tokens);
+
+        assert!(lines.iter().all(|line| line.len() <= 79));
+        assert_eq!(lines.join(" "), tokens.join(" "));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(UNIX_EPOCH), "1970.01.01");
+        assert_eq!(
+            format_date(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000.02.29"
+        );
+        assert_eq!(
+            format_date(UNIX_EPOCH + Duration::from_secs(1_792_281_600)),
+            "2026.10.18"
+        );
+    }
+
+    fn pgn_movetext(game: &Game) -> String {
+        game.to_pgn()
+            .split("\n\n")
+            .nth(1)
+            .unwrap()
+            .trim()
+            .replace('\n', " ")
+    }
+}
@@ -1,46 +1,35 @@
 use crate::logic::basic::Coordinate;
+use crate::logic::bitboard;
 use crate::logic::board::Board;
 
-use super::queen::{all_moves_diagonal, is_diagonal, piece_between_diagonal};
-use super::{is_friendly_fire, Move, MoveError};
+use super::queen::all_moves_diagonal;
+use super::{check_regular_move, Move, MoveError};
 
 pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
     all_moves_diagonal(board, from)
 }
 
-pub fn move_piece(board: &Board, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-    match a_move {
-        Move::Promotion(..) => Err(MoveError::IllegalMove),
-        Move::Regular(to) => {
-            if from == to {
-                return Err(MoveError::IllegalMove);
-            }
-
-            if is_friendly_fire(board, to) {
-                return Err(MoveError::IllegalMove);
-            }
+pub fn check_move(board: &Board, a_move: &Move) -> Result<(), MoveError> {
+    check_regular_move(board, a_move, bitboard::bishop_attacks)
+}
 
-            if is_diagonal(from, to) && !piece_between_diagonal(board, from, to) {
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                Ok(new_board)
-            } else {
-                Err(MoveError::IllegalMove)
-            }
-        }
-    }
+pub fn move_piece(board: &Board, a_move: &Move) -> Result<Board, MoveError> {
+    check_move(board, a_move)?;
+    Ok(board.made(a_move))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::logic::board::Board;
-    use crate::logic::pieces::tests::{assert_all_moves_valid, assert_valid_in_all_moves, c, m};
+    use crate::logic::pieces::tests::{
+        assert_all_moves_valid, assert_valid_in_all_moves, board_from_configuration, c, m,
+    };
     use crate::logic::pieces::Piece;
 
     use super::{all_moves, move_piece};
 
     fn test_board() -> Board {
-        Board::from_configuration([
+        board_from_configuration([
             [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
             [' ', 'p', ' ', ' ', 'P', ' ', 'B', ' '],
             [' ', ' ', 'b', ' ', ' ', ' ', ' ', ' '],
@@ -94,25 +83,25 @@ mod tests {
     fn test_moves() {
         let board = test_board();
         // - Straight
-        assert!(move_piece(&board, &c(6, 1), &m(5, 1)).is_err());
+        assert!(move_piece(&board, &m(&board, c(6, 1), c(5, 1))).is_err());
         // - Diagonal
-        move_piece(&board, &c(6, 1), &m(7, 2)).unwrap();
-        move_piece(&board, &c(6, 1), &m(5, 0)).unwrap();
+        move_piece(&board, &m(&board, c(6, 1), c(7, 2))).unwrap();
+        move_piece(&board, &m(&board, c(6, 1), c(5, 0))).unwrap();
 
         let turned = board.turned();
         // - Straight
-        assert!(move_piece(&turned, &c(2, 2), &m(7, 2)).is_err());
+        assert!(move_piece(&turned, &m(&turned, c(2, 2), c(7, 2))).is_err());
         // - Diagonal
-        move_piece(&turned, &c(2, 2), &m(5, 5)).unwrap();
-        move_piece(&turned, &c(2, 2), &m(4, 0)).unwrap();
+        move_piece(&turned, &m(&turned, c(2, 2), c(5, 5))).unwrap();
+        move_piece(&turned, &m(&turned, c(2, 2), c(4, 0))).unwrap();
     }
 
     #[test]
     fn test_captures() {
         let board = test_board();
-        move_piece(&board, &c(6, 1), &m(4, 3)).unwrap();
+        move_piece(&board, &m(&board, c(6, 1), c(4, 3))).unwrap();
 
         let turned = board.turned();
-        move_piece(&turned, &c(2, 2), &m(6, 6)).unwrap();
+        move_piece(&turned, &m(&turned, c(2, 2), c(6, 6))).unwrap();
     }
 }
@@ -1,137 +1,107 @@
 use crate::logic::basic::Coordinate;
+use crate::logic::bitboard;
 use crate::logic::board::{Board, TileContent, BOARD_SIZE};
+use crate::logic::castling::{king_home, CastlingSide};
 
-use super::queen::piece_between_straight;
-use super::{is_friendly_fire, Move, MoveError, PieceType};
+use super::{check_regular_move, first_blocker, new_move, new_moves, Move, MoveError, PieceType};
 
 pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
     let (x, y) = from.values();
-    let mut moves = Vec::with_capacity(8);
 
     // - Regular
-    for delta_x in -1..=1 {
-        for delta_y in -1..=1 {
-            if delta_x == 0 && delta_y == 0 {
-                continue;
-            }
-
-            let to_x = x as i16 + delta_x;
-            let to_y = y as i16 + delta_y;
-
-            if to_x < 0 || to_x >= BOARD_SIZE as i16 || to_y < 0 || to_y >= BOARD_SIZE as i16 {
-                continue;
-            }
-
-            let coord = Coordinate::try_new(to_x as usize, to_y as usize).unwrap();
-            if !is_friendly_fire(board, &coord) {
-                moves.push(Move::Regular(coord));
-            }
-        }
-    }
+    let mut moves = new_moves(board, from, bitboard::king_attacks(from));
 
     // -- Castling
     if x + 2 < BOARD_SIZE {
         let to = Coordinate::try_new(x + 2, y).unwrap();
-        if get_castling_rook(board, from, &to).is_some() {
-            moves.push(Move::Regular(to));
+        if check_castling(board, from, &to).is_ok() {
+            moves.push(new_move(board, from, to));
         }
     }
     if x >= 2 {
         let to = Coordinate::try_new(x - 2, y).unwrap();
-        if get_castling_rook(board, from, &to).is_some() {
-            moves.push(Move::Regular(to));
+        if check_castling(board, from, &to).is_ok() {
+            moves.push(new_move(board, from, to));
         }
     }
 
     moves
 }
 
-pub fn move_piece(board: &Board, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-    match a_move {
-        Move::Promotion(..) => Err(MoveError::IllegalMove),
-        Move::Regular(to) => {
-            if from == to {
-                return Err(MoveError::IllegalMove);
-            }
+pub fn check_move(board: &Board, a_move: &Move) -> Result<(), MoveError> {
+    if a_move.is_promotion() {
+        return Err(MoveError::UnexpectedPromotion);
+    }
 
-            let (from_x, from_y) = from.values();
-            let (to_x, to_y) = to.values();
-
-            let delta_x = from_x.abs_diff(to_x);
-            let delta_y = from_y.abs_diff(to_y);
-
-            if delta_x < 2 && delta_y < 2 {
-                if is_friendly_fire(board, to) {
-                    return Err(MoveError::IllegalMove);
-                }
-
-                // - Regular move
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                Ok(new_board)
-            } else if let Some(rook_coord) = get_castling_rook(board, from, to) {
-                // - Castling
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                new_board.move_tile(&rook_coord, from);
-                Ok(new_board)
-            } else {
-                Err(MoveError::IllegalMove)
-            }
-        }
+    if a_move.is_castling() {
+        // - Castling
+        check_castling(board, &a_move.from, &a_move.to)
+    } else {
+        // - Regular move
+        check_regular_move(board, a_move, |from, _| bitboard::king_attacks(from))
     }
 }
 
-fn get_castling_rook(board: &Board, from: &Coordinate, to: &Coordinate) -> Option<Coordinate> {
-    let (from_x, from_y) = from.values();
-    let (to_x, to_y) = to.values();
-    let delta_x = from_x.abs_diff(to_x);
-    let delta_y = from_y.abs_diff(to_y);
+pub fn move_piece(board: &Board, a_move: &Move) -> Result<Board, MoveError> {
+    check_move(board, a_move)?;
+    Ok(board.made(a_move))
+}
 
-    if delta_y != 0 || delta_x != 2 {
-        return None;
+fn check_castling(board: &Board, from: &Coordinate, to: &Coordinate) -> Result<(), MoveError> {
+    // King moves two squares towards a rook on its home square
+    let side = CastlingSide::from_king_target(from, to);
+
+    let is_own = |coordinate: &Coordinate, piece_type: PieceType| {
+        matches!(
+            board.get_tile(coordinate),
+            TileContent::Piece(piece) if piece.player == board.turn && piece.piece_type == piece_type
+        )
+    };
+    let rook_coord = side.rook_home(&board.turn);
+
+    if *from != king_home(&board.turn)
+        || !board.castling.has(&board.turn, &side)
+        || !is_own(from, PieceType::King)
+        || !is_own(&rook_coord, PieceType::Rook)
+    {
+        return Err(MoveError::CastlingUnavailable(side));
     }
 
-    if let TileContent::Piece(piece) = board.get_tile(from) {
-        if piece.moved {
-            return None;
-        }
-    } else {
-        return None;
+    if let Some(blocker) = first_blocker(board, from, &rook_coord) {
+        return Err(MoveError::PathBlocked(blocker));
     }
 
-    let rook_x = if from_x < to_x { BOARD_SIZE - 1 } else { 0 };
-    let rook_coord = Coordinate::try_new(rook_x, from_y).unwrap();
+    // The king may not castle out of, through or into check
+    let enemy = board.turn.other();
 
-    if let TileContent::Piece(piece) = board.get_tile(&rook_coord) {
-        if piece.moved {
-            return None;
-        }
-        if !matches!(piece.piece_type, PieceType::Rook) {
-            return None;
-        }
-        if piece.player != board.turn {
-            return None;
-        }
-        if piece_between_straight(board, from, &rook_coord) {
-            return None;
-        }
-        Some(rook_coord)
-    } else {
-        None
+    match [from.clone(), castling_pass(from, to), to.clone()]
+        .into_iter()
+        .find(|coord| board.is_square_attacked(coord, &enemy))
+    {
+        Some(attacked) => Err(MoveError::CastlingThroughCheck(attacked)),
+        None => Ok(()),
     }
 }
 
+fn castling_pass(from: &Coordinate, to: &Coordinate) -> Coordinate {
+    // Square the king crosses, which is where the rook ends up
+    Coordinate::try_new((from.xv() + to.xv()) / 2, from.yv()).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::logic::basic::Player;
     use crate::logic::board::{Board, TileContent};
-    use crate::logic::pieces::tests::{assert_all_moves_valid, assert_valid_in_all_moves, c, m};
-    use crate::logic::pieces::{Piece, PieceType};
+    use crate::logic::castling::CastlingSide;
+    use crate::logic::pieces::tests::{
+        assert_all_moves_valid, assert_valid_in_all_moves, board_from_configuration, c, m,
+    };
+    use crate::logic::pieces::{rook, Piece, PieceType};
 
     use super::{all_moves, move_piece};
 
     fn test_board() -> Board {
-        Board::from_configuration([
+        board_from_configuration([
             ['r', ' ', ' ', 'k', ' ', ' ', ' ', ' '],
             [' ', 'p', ' ', ' ', 'P', ' ', 'B', ' '],
             [' ', ' ', 'b', ' ', ' ', ' ', ' ', ' '],
@@ -185,27 +155,101 @@ mod tests {
     fn test_castling() {
         let board = test_board();
         {
-            let new_board = move_piece(&board, &c(4, 7), &m(6, 7)).unwrap();
+            let new_board = move_piece(&board, &m(&board, c(4, 7), c(6, 7))).unwrap();
             assert!(matches!(
                 new_board.get_tile(&c(6, 7)),
                 TileContent::Piece(_)
             ));
             if let TileContent::Piece(piece) = new_board.get_tile(&c(6, 7)) {
                 assert!(matches!(piece.piece_type, PieceType::King));
-                assert!(piece.moved);
             }
             assert!(matches!(
-                new_board.get_tile(&c(4, 7)),
+                new_board.get_tile(&c(5, 7)),
                 TileContent::Piece(_)
             ));
-            if let TileContent::Piece(piece) = new_board.get_tile(&c(4, 7)) {
+            if let TileContent::Piece(piece) = new_board.get_tile(&c(5, 7)) {
                 assert!(matches!(piece.piece_type, PieceType::Rook));
-                assert!(piece.moved);
             }
+            assert!(matches!(new_board.get_tile(&c(4, 7)), TileContent::Empty));
+            assert!(matches!(new_board.get_tile(&c(7, 7)), TileContent::Empty));
+            assert!(!new_board.castling.has_any(&Player::White));
         }
 
         // This was a faulty result. It makes no sense at all.
         // Just keeping it here to assure it does not come back
-        assert!(move_piece(&board, &c(4, 7), &m(6, 0)).is_err());
+        assert!(move_piece(&board, &m(&board, c(4, 7), c(6, 0))).is_err());
+    }
+
+    #[test]
+    fn test_castling_rights() {
+        let board = test_board();
+        assert!(board.castling.has(&Player::White, &CastlingSide::KingSide));
+        assert!(!board.castling.has(&Player::White, &CastlingSide::QueenSide));
+
+        // Moving the rook loses the right, even when it returns
+        let moved = rook::move_piece(&board, &m(&board, c(7, 7), c(6, 7))).unwrap();
+        let moved = moved.turned();
+        let returned = rook::move_piece(&moved, &m(&moved, c(6, 7), c(7, 7))).unwrap();
+        assert!(!returned.castling.has_any(&Player::White));
+        let returned = returned.turned();
+        assert!(move_piece(&returned, &m(&returned, c(4, 7), c(6, 7))).is_err());
+
+        // Capturing a rook removes the right of its owner
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+        let captured = rook::move_piece(&board, &m(&board, c(0, 0), c(0, 7))).unwrap();
+        assert!(captured
+            .castling
+            .has(&Player::White, &CastlingSide::KingSide));
+        assert!(!captured
+            .castling
+            .has(&Player::White, &CastlingSide::QueenSide));
+        assert!(captured
+            .castling
+            .has(&Player::Black, &CastlingSide::KingSide));
+        assert!(!captured
+            .castling
+            .has(&Player::Black, &CastlingSide::QueenSide));
+    }
+
+    #[test]
+    fn test_castling_queen_side() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+        let new_board = move_piece(&board, &m(&board, c(4, 0), c(2, 0))).unwrap();
+
+        if let TileContent::Piece(piece) = new_board.get_tile(&c(3, 0)) {
+            assert!(matches!(piece.piece_type, PieceType::Rook));
+        } else {
+            panic!("Rook did not move next to the king");
+        }
+        assert!(matches!(new_board.get_tile(&c(0, 0)), TileContent::Empty));
+    }
+
+    #[test]
+    fn test_castling_check() {
+        // Out of check
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        assert!(move_piece(&board, &m(&board, c(4, 7), c(6, 7))).is_err());
+        assert!(move_piece(&board, &m(&board, c(4, 7), c(2, 7))).is_err());
+
+        // Through check
+        let board = Board::from_fen("5rk1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        assert!(move_piece(&board, &m(&board, c(4, 7), c(6, 7))).is_err());
+        move_piece(&board, &m(&board, c(4, 7), c(2, 7))).unwrap();
+
+        // Into check
+        let board = Board::from_fen("2r3k1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        move_piece(&board, &m(&board, c(4, 7), c(6, 7))).unwrap();
+        assert!(move_piece(&board, &m(&board, c(4, 7), c(2, 7))).is_err());
+
+        // The rook may pass an attacked square
+        let board = Board::from_fen("1r4k1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+        move_piece(&board, &m(&board, c(4, 7), c(2, 7))).unwrap();
+
+        for board in [
+            Board::from_fen("4r1k1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap(),
+            Board::from_fen("5rk1/8/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap(),
+        ] {
+            assert!(!all_moves(&board, &c(4, 7)).contains(&m(&board, c(4, 7), c(6, 7))));
+        }
     }
 }
@@ -1,90 +1,34 @@
 use crate::logic::basic::Coordinate;
-use crate::logic::board::{Board, TileContent, BOARD_SIZE};
+use crate::logic::bitboard;
+use crate::logic::board::Board;
 
-use super::{Move, MoveError};
+use super::{check_regular_move, new_moves, Move, MoveError};
 
 pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
-    let (x, y) = from.values();
-    let mut moves = Vec::with_capacity(8);
-
-    for delta_x in 1..=2 {
-        let delta_y = 3 - delta_x;
-
-        if x + delta_x < BOARD_SIZE && y + delta_y < BOARD_SIZE {
-            let coord = Coordinate::try_new(x + delta_x, y + delta_y).unwrap();
-            if !is_friendly_fire(board, &coord) {
-                moves.push(Move::Regular(coord));
-            }
-        }
-        if x >= delta_x && y + delta_y < BOARD_SIZE {
-            let coord = Coordinate::try_new(x - delta_x, y + delta_y).unwrap();
-            if !is_friendly_fire(board, &coord) {
-                moves.push(Move::Regular(coord));
-            }
-        }
-        if x + delta_x < BOARD_SIZE && y >= delta_y {
-            let coord = Coordinate::try_new(x + delta_x, y - delta_y).unwrap();
-            if !is_friendly_fire(board, &coord) {
-                moves.push(Move::Regular(coord));
-            }
-        }
-        if x >= delta_x && y >= delta_y {
-            let coord = Coordinate::try_new(x - delta_x, y - delta_y).unwrap();
-            if !is_friendly_fire(board, &coord) {
-                moves.push(Move::Regular(coord));
-            }
-        }
-    }
-
-    moves
+    new_moves(board, from, bitboard::knight_attacks(from))
 }
 
-fn is_friendly_fire(board: &Board, coordinate: &Coordinate) -> bool {
-    match board.get_tile(coordinate) {
-        TileContent::Piece(piece) => piece.player == board.turn,
-        TileContent::Empty => false,
-    }
+pub fn check_move(board: &Board, a_move: &Move) -> Result<(), MoveError> {
+    check_regular_move(board, a_move, |from, _| bitboard::knight_attacks(from))
 }
 
-pub fn move_piece(board: &Board, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-    match a_move {
-        Move::Promotion(..) => Err(MoveError::IllegalMove),
-        Move::Regular(to) => {
-            if from == to {
-                return Err(MoveError::IllegalMove);
-            }
-
-            if is_friendly_fire(board, to) {
-                return Err(MoveError::IllegalMove);
-            }
-
-            let (from_x, from_y) = from.values();
-            let (to_x, to_y) = to.values();
-
-            let delta_x = from_x.abs_diff(to_x);
-            let delta_y = from_y.abs_diff(to_y);
-
-            if delta_x == 2 && delta_y == 1 || delta_x == 1 && delta_y == 2 {
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                Ok(new_board)
-            } else {
-                Err(MoveError::IllegalMove)
-            }
-        }
-    }
+pub fn move_piece(board: &Board, a_move: &Move) -> Result<Board, MoveError> {
+    check_move(board, a_move)?;
+    Ok(board.made(a_move))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::logic::board::Board;
-    use crate::logic::pieces::tests::{assert_all_moves_valid, assert_valid_in_all_moves, c, m};
+    use crate::logic::pieces::tests::{
+        assert_all_moves_valid, assert_valid_in_all_moves, board_from_configuration, c, m,
+    };
     use crate::logic::pieces::Piece;
 
     use super::{all_moves, move_piece};
 
     fn test_board() -> Board {
-        Board::from_configuration([
+        board_from_configuration([
             [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
             [' ', 'p', ' ', ' ', 'P', ' ', 'B', ' '],
             [' ', ' ', 'b', ' ', ' ', ' ', ' ', ' '],
@@ -137,18 +81,18 @@ mod tests {
     #[test]
     fn test_moves() {
         let board = test_board();
-        move_piece(&board, &c(3, 5), &m(2, 3)).unwrap();
-        assert!(move_piece(&board, &c(3, 5), &m(1, 6)).is_err());
-        move_piece(&board, &c(3, 5), &m(1, 4)).unwrap();
-        move_piece(&board, &c(3, 5), &m(2, 7)).unwrap();
-        move_piece(&board, &c(3, 5), &m(4, 7)).unwrap();
-        assert!(move_piece(&board, &c(3, 5), &m(1, 7)).is_err());
+        move_piece(&board, &m(&board, c(3, 5), c(2, 3))).unwrap();
+        assert!(move_piece(&board, &m(&board, c(3, 5), c(1, 6))).is_err());
+        move_piece(&board, &m(&board, c(3, 5), c(1, 4))).unwrap();
+        move_piece(&board, &m(&board, c(3, 5), c(2, 7))).unwrap();
+        move_piece(&board, &m(&board, c(3, 5), c(4, 7))).unwrap();
+        assert!(move_piece(&board, &m(&board, c(3, 5), c(1, 7))).is_err());
     }
 
     #[test]
     fn test_captures() {
         let board = test_board();
-        move_piece(&board, &c(3, 5), &m(4, 3)).unwrap();
-        assert!(move_piece(&board, &c(3, 5), &m(5, 6)).is_err());
+        move_piece(&board, &m(&board, c(3, 5), c(4, 3))).unwrap();
+        assert!(move_piece(&board, &m(&board, c(3, 5), c(5, 6))).is_err());
     }
 }
@@ -10,9 +10,10 @@ use std::fmt;
 
 use super::basic::Coordinate;
 use super::basic::Player;
+use super::bitboard::{self, Bitboard, EMPTY};
 use super::board::Board;
 use super::board::TileContent;
-use crate::utils::ValueError;
+use super::castling::CastlingSide;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum PieceType {
@@ -25,7 +26,7 @@ pub enum PieceType {
 }
 
 impl PieceType {
-    pub fn from_letter(letter: char) -> Result<Self, ValueError> {
+    pub fn from_letter(letter: char) -> Result<Self, InvalidPieceLetter> {
         Ok(match letter {
             'K' => Self::King,
             'Q' => Self::Queen,
@@ -33,10 +34,32 @@ impl PieceType {
             'B' => Self::Bishop,
             'N' => Self::Knight,
             'P' => Self::Pawn,
-            _ => Err(ValueError)?,
+            _ => Err(InvalidPieceLetter(letter))?,
         })
     }
 
+    pub fn to_label(&self) -> &str {
+        match self {
+            Self::King => "king",
+            Self::Queen => "queen",
+            Self::Rook => "rook",
+            Self::Bishop => "bishop",
+            Self::Knight => "knight",
+            Self::Pawn => "pawn",
+        }
+    }
+
+    pub fn to_letter(&self) -> char {
+        match self {
+            Self::King => 'K',
+            Self::Queen => 'Q',
+            Self::Rook => 'R',
+            Self::Bishop => 'B',
+            Self::Knight => 'N',
+            Self::Pawn => 'P',
+        }
+    }
+
     pub fn get_symbol(&self, player: &Player) -> &str {
         match player {
             Player::White => match self {
@@ -58,28 +81,27 @@ impl PieceType {
         }
     }
 
-    pub fn can_move(&self, board: &Board, from: &Coordinate, to: &Coordinate) -> bool {
+    pub fn check_move(&self, board: &Board, a_move: &Move) -> Result<(), MoveError> {
         // Piece at `from` and `piece` is from player with turn already checked
-        matches!(
-            self.move_piece(board, from, &Move::Regular(to.clone())),
-            Ok(_) | Err(MoveError::PromotionRequired)
-        )
+        match self {
+            Self::Pawn => pawn::check_move(board, a_move),
+            Self::Queen => queen::check_move(board, a_move),
+            Self::Rook => rook::check_move(board, a_move),
+            Self::Bishop => bishop::check_move(board, a_move),
+            Self::Knight => knight::check_move(board, a_move),
+            Self::King => king::check_move(board, a_move),
+        }
     }
 
-    pub fn move_piece(
-        &self,
-        board: &Board,
-        from: &Coordinate,
-        a_move: &Move,
-    ) -> Result<Board, MoveError> {
+    pub fn move_piece(&self, board: &Board, a_move: &Move) -> Result<Board, MoveError> {
         // Piece at `from` and `piece` is from player with turn already checked
         match self {
-            Self::Pawn => pawn::move_piece(board, from, a_move),
-            Self::Queen => queen::move_piece(board, from, a_move),
-            Self::Rook => rook::move_piece(board, from, a_move),
-            Self::Bishop => bishop::move_piece(board, from, a_move),
-            Self::Knight => knight::move_piece(board, from, a_move),
-            Self::King => king::move_piece(board, from, a_move),
+            Self::Pawn => pawn::move_piece(board, a_move),
+            Self::Queen => queen::move_piece(board, a_move),
+            Self::Rook => rook::move_piece(board, a_move),
+            Self::Bishop => bishop::move_piece(board, a_move),
+            Self::Knight => knight::move_piece(board, a_move),
+            Self::King => king::move_piece(board, a_move),
         }
     }
 
@@ -99,54 +121,60 @@ impl PieceType {
 pub struct Piece {
     pub player: Player,
     pub piece_type: PieceType,
-    pub moved: bool,
 }
 
 impl Piece {
-    pub fn from_letter(letter: char) -> Result<Self, ValueError> {
+    pub fn from_letter(letter: char) -> Result<Self, InvalidPieceLetter> {
         let player = match letter {
             'A'..='Z' => Player::White,
             'a'..='z' => Player::Black,
-            _ => Err(ValueError)?,
+            _ => Err(InvalidPieceLetter(letter))?,
         };
 
         let upper_letter = letter.to_ascii_uppercase();
-        let piece_type = PieceType::from_letter(upper_letter)?;
+        let piece_type =
+            PieceType::from_letter(upper_letter).map_err(|_| InvalidPieceLetter(letter))?;
 
-        Ok(Self {
-            player,
-            piece_type,
-            moved: false,
-        })
+        Ok(Self { player, piece_type })
+    }
+
+    pub fn to_letter(&self) -> char {
+        let letter = self.piece_type.to_letter();
+
+        match self.player {
+            Player::White => letter,
+            Player::Black => letter.to_ascii_lowercase(),
+        }
     }
 
     pub fn get_symbol(&self) -> &str {
         self.piece_type.get_symbol(&self.player)
     }
 
-    pub fn can_move(&self, board: &Board, from: &Coordinate, to: &Coordinate) -> bool {
+    pub fn check_move(&self, board: &Board, a_move: &Move) -> Result<(), MoveError> {
         // Piece at `from` and already checked
-
-        if self.player != board.turn {
-            return false;
-        }
-
-        self.piece_type.can_move(board, from, to)
+        self.check_move_fits(board, a_move)?;
+        self.piece_type.check_move(board, a_move)
     }
 
-    pub fn move_piece(
-        &self,
-        board: &Board,
-        from: &Coordinate,
-        a_move: &Move,
-    ) -> Result<Board, MoveError> {
+    pub fn move_piece(&self, board: &Board, a_move: &Move) -> Result<Board, MoveError> {
         // Piece at `from` and already checked
+        self.check_move_fits(board, a_move)?;
+        self.piece_type.move_piece(board, a_move)
+    }
 
+    fn check_move_fits(&self, board: &Board, a_move: &Move) -> Result<(), MoveError> {
         if self.player != board.turn {
-            return Err(MoveError::IllegalMove);
+            return Err(MoveError::NotYourPiece(a_move.from.clone()));
+        }
+
+        // Moves carry what they capture and how they move, which has to fit the board
+        let expected = Move::new(board, &a_move.from, &a_move.to, a_move.promotion.clone());
+        if a_move.piece != *self || expected.ok().as_ref() != Some(a_move) {
+            return Err(MoveError::MoveMismatch(a_move.clone()));
         }
 
-        self.piece_type.move_piece(board, from, a_move)
+        Ok(())
     }
 
     pub fn all_moves(&self, board: &Board, from: &Coordinate) -> Vec<Move> {
@@ -158,59 +186,311 @@ impl Piece {
         new_piece.piece_type = new_type;
         new_piece
     }
+}
 
-    pub fn moved(&self) -> Self {
-        let mut new_piece = self.clone();
-        new_piece.moved = true;
-        new_piece
-    }
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoveKind {
+    Regular,
+    DoublePush,
+    EnPassant,
+    Castling,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum Move {
-    Regular(Coordinate),
-    Promotion(Coordinate, PieceType),
+pub struct Move {
+    pub from: Coordinate,
+    pub to: Coordinate,
+    pub piece: Piece,
+    pub captured: Option<Piece>,
+    pub promotion: Option<PieceType>,
+    pub kind: MoveKind,
 }
 
 impl Move {
+    pub fn new(
+        board: &Board,
+        from: &Coordinate,
+        to: &Coordinate,
+        promotion: Option<PieceType>,
+    ) -> Result<Self, MoveError> {
+        let piece = match board.get_tile(from) {
+            TileContent::Piece(piece) => piece.clone(),
+            TileContent::Empty => return Err(MoveError::EmptySquare(from.clone())),
+        };
+
+        let delta_x = from.xv().abs_diff(to.xv());
+        let delta_y = from.yv().abs_diff(to.yv());
+
+        let kind = match piece.piece_type {
+            PieceType::King if delta_x == 2 && delta_y == 0 => MoveKind::Castling,
+            PieceType::Pawn if delta_x == 0 && delta_y == 2 => MoveKind::DoublePush,
+            PieceType::Pawn if delta_x == 1 && board.get_tile(to) == &TileContent::Empty => {
+                MoveKind::EnPassant
+            }
+            _ => MoveKind::Regular,
+        };
+
+        let mut a_move = Self {
+            from: from.clone(),
+            to: to.clone(),
+            piece,
+            captured: None,
+            promotion,
+            kind,
+        };
+
+        if let TileContent::Piece(captured) = board.get_tile(&a_move.captured_coordinate()) {
+            a_move.captured = Some(captured.clone());
+        }
+
+        Ok(a_move)
+    }
+
     pub fn get_to(&self) -> &Coordinate {
-        match self {
-            Move::Regular(to) => to,
-            Move::Promotion(to, _) => to,
+        &self.to
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    pub fn is_promotion(&self) -> bool {
+        self.promotion.is_some()
+    }
+
+    pub fn is_castling(&self) -> bool {
+        self.kind == MoveKind::Castling
+    }
+
+    pub fn is_en_passant(&self) -> bool {
+        self.kind == MoveKind::EnPassant
+    }
+
+    pub fn is_double_push(&self) -> bool {
+        self.kind == MoveKind::DoublePush
+    }
+
+    pub fn castling_rook(&self) -> Option<(Coordinate, Coordinate)> {
+        // The rook moves from its home square to the square the king crosses
+        if !self.is_castling() {
+            return None;
+        }
+
+        let side = CastlingSide::from_king_target(&self.from, &self.to);
+        let rook_to = Coordinate::try_new((self.from.xv() + self.to.xv()) / 2, self.from.yv());
+        Some((side.rook_home(&self.piece.player), rook_to.unwrap()))
+    }
+
+    pub fn captured_coordinate(&self) -> Coordinate {
+        // The pawn captured en passant stands next to the moving pawn, not on its target
+        match self.kind {
+            MoveKind::EnPassant => Coordinate::try_new(self.to.xv(), self.from.yv()).unwrap(),
+            _ => self.to.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            self.from.to_field_name(),
+            self.to.to_field_name()
+        )?;
+
+        match &self.promotion {
+            Some(piece_type) => write!(f, "{}", piece_type.to_letter()),
+            None => Ok(()),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidPieceLetter(pub char);
+
+impl Error for InvalidPieceLetter {}
+
+impl fmt::Display for InvalidPieceLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "'{}' is not a valid piece letter", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveError {
-    IllegalMove,
+    EmptySquare(Coordinate),
+    NotYourPiece(Coordinate),
+    MoveMismatch(Move),
+    Unreachable(PieceType, Coordinate, Coordinate),
+    PathBlocked(Coordinate),
+    OwnPieceOnTarget(Coordinate),
+    CastlingUnavailable(CastlingSide),
+    CastlingThroughCheck(Coordinate),
     PromotionRequired,
-    IsCheck,
+    InvalidPromotion(PieceType),
+    UnexpectedPromotion,
+    IsCheck(PieceType, Coordinate),
+    GameOver,
 }
+
 impl Error for MoveError {}
+
 impl fmt::Display for MoveError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MoveError")
+        match self {
+            Self::EmptySquare(from) => write!(f, "There is no piece on {}", from.to_field_name()),
+            Self::NotYourPiece(from) => write!(
+                f,
+                "The piece on {} belongs to the opponent",
+                from.to_field_name()
+            ),
+            Self::MoveMismatch(a_move) => {
+                write!(f, "Move {} does not fit the current position", a_move)
+            }
+            Self::Unreachable(piece_type, from, to) => write!(
+                f,
+                "A {} cannot move from {} to {}",
+                piece_type.to_label(),
+                from.to_field_name(),
+                to.to_field_name()
+            ),
+            Self::PathBlocked(blocker) => write!(
+                f,
+                "The way is blocked by the piece on {}",
+                blocker.to_field_name()
+            ),
+            Self::OwnPieceOnTarget(to) => {
+                write!(f, "Cannot capture your own piece on {}", to.to_field_name())
+            }
+            Self::CastlingUnavailable(side) => {
+                write!(f, "Castling {} is no longer possible", side.to_label())
+            }
+            Self::CastlingThroughCheck(square) => write!(
+                f,
+                "Cannot castle out of, through or into check, {} is attacked",
+                square.to_field_name()
+            ),
+            Self::PromotionRequired => write!(f, "A pawn reaching the last rank must be promoted"),
+            Self::InvalidPromotion(piece_type) => {
+                write!(
+                    f,
+                    "A pawn cannot be promoted to a {}",
+                    piece_type.to_label()
+                )
+            }
+            Self::UnexpectedPromotion => {
+                write!(f, "Only a pawn reaching the last rank can be promoted")
+            }
+            Self::IsCheck(piece_type, checker) => write!(
+                f,
+                "Cannot make this move, the {} on {} would give check to your king",
+                piece_type.to_label(),
+                checker.to_field_name()
+            ),
+            Self::GameOver => write!(f, "The game is already over"),
+        }
     }
 }
 
-pub fn is_friendly_fire(board: &Board, coordinate: &Coordinate) -> bool {
+pub(crate) fn new_move(board: &Board, from: &Coordinate, to: Coordinate) -> Move {
+    // Piece at `from` already checked
+    Move::new(board, from, &to, None).unwrap()
+}
+
+pub(crate) fn new_moves(board: &Board, from: &Coordinate, targets: Bitboard) -> Vec<Move> {
+    // Piece at `from` already checked, targets of the own pieces are skipped
+    let targets = targets & !board.bitboards().occupancy(&board.turn);
+    bitboard::coordinates(targets)
+        .map(|to| new_move(board, from, to))
+        .collect()
+}
+
+pub(crate) fn is_friendly_fire(board: &Board, coordinate: &Coordinate) -> bool {
     match board.get_tile(coordinate) {
         TileContent::Piece(piece) => piece.player == board.turn,
         TileContent::Empty => false,
     }
 }
 
+pub(crate) fn check_regular_move(
+    board: &Board,
+    a_move: &Move,
+    attacks: fn(&Coordinate, Bitboard) -> Bitboard,
+) -> Result<(), MoveError> {
+    // Moves of every piece that captures the way it moves, so all except pawns
+    if a_move.is_promotion() {
+        return Err(MoveError::UnexpectedPromotion);
+    }
+
+    let from = &a_move.from;
+    let to = &a_move.to;
+
+    if attacks(from, EMPTY) & bitboard::bit(to) == EMPTY {
+        return Err(MoveError::Unreachable(
+            a_move.piece.piece_type.clone(),
+            from.clone(),
+            to.clone(),
+        ));
+    }
+
+    if let Some(blocker) = first_blocker(board, from, to) {
+        return Err(MoveError::PathBlocked(blocker));
+    }
+
+    if is_friendly_fire(board, to) {
+        return Err(MoveError::OwnPieceOnTarget(to.clone()));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn first_blocker(
+    board: &Board,
+    from: &Coordinate,
+    to: &Coordinate,
+) -> Option<Coordinate> {
+    // Occupied square closest to `from` strictly between both squares on a line
+    let blockers = squares_between(from, to) & board.bitboards().all();
+
+    bitboard::coordinates(blockers).min_by_key(|blocker| {
+        from.xv()
+            .abs_diff(blocker.xv())
+            .max(from.yv().abs_diff(blocker.yv()))
+    })
+}
+
+fn squares_between(from: &Coordinate, to: &Coordinate) -> Bitboard {
+    // Looking from both ends with the other one as the only blocker leaves the squares in between
+    if from == to {
+        return EMPTY;
+    }
+
+    let delta_x = from.xv().abs_diff(to.xv());
+    let delta_y = from.yv().abs_diff(to.yv());
+
+    let attacks = if delta_x == 0 || delta_y == 0 {
+        bitboard::rook_attacks
+    } else if delta_x == delta_y {
+        bitboard::bishop_attacks
+    } else {
+        return EMPTY;
+    };
+
+    attacks(from, bitboard::bit(to)) & attacks(to, bitboard::bit(from))
+}
+
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use std::fmt::Debug;
 
     use crate::logic::basic::Coordinate;
     use crate::logic::board::{Board, TileContent, BOARD_SIZE};
+    use crate::logic::castling::CastlingSide;
 
     use super::{Move, MoveError, Piece, PieceType};
 
-    type MovePieceFn = fn(&Board, &Coordinate, &Move) -> Result<Board, MoveError>;
+    type MovePieceFn = fn(&Board, &Move) -> Result<Board, MoveError>;
     type AllMovesFn = fn(&Board, &Coordinate) -> Vec<Move>;
 
     pub fn assert_vecs_same_elements<T, F, K>(actual: &mut Vec<T>, expected: &mut Vec<T>, keyf: &F)
@@ -248,7 +528,7 @@ mod tests {
                 assert_all_moves_valid_from(
                     &board_with_piece,
                     &from,
-                    &all_moves(board, &from),
+                    &all_moves(&board_with_piece, &from),
                     move_piece,
                 );
             }
@@ -262,7 +542,7 @@ mod tests {
         move_piece: MovePieceFn,
     ) {
         for a_move in moves {
-            match move_piece(board, from, a_move) {
+            match move_piece(board, a_move) {
                 Ok(new_board) => {
                     assert!(matches!(
                         new_board.get_tile(a_move.get_to()),
@@ -311,22 +591,18 @@ mod tests {
             for y in 0..BOARD_SIZE {
                 let to = c(x, y);
 
-                let move_regular = Move::Regular(to.clone());
-                if move_piece(board, from, &move_regular).is_ok() {
-                    valid_moves.push(move_regular);
-                }
-
-                for piece_type in [
-                    PieceType::King,
-                    PieceType::Queen,
-                    PieceType::Rook,
-                    PieceType::Bishop,
-                    PieceType::Knight,
-                    PieceType::Pawn,
+                for promotion in [
+                    None,
+                    Some(PieceType::King),
+                    Some(PieceType::Queen),
+                    Some(PieceType::Rook),
+                    Some(PieceType::Bishop),
+                    Some(PieceType::Knight),
+                    Some(PieceType::Pawn),
                 ] {
-                    let move_promotion = Move::Promotion(to.clone(), piece_type);
-                    if move_piece(board, from, &move_promotion).is_ok() {
-                        valid_moves.push(move_promotion);
+                    let a_move = Move::new(board, from, &to, promotion).unwrap();
+                    if move_piece(board, &a_move).is_ok() {
+                        valid_moves.push(a_move);
                     }
                 }
             }
@@ -335,18 +611,128 @@ mod tests {
         assert_vecs_same_elements(
             &mut valid_moves,
             &mut all_moves(board, from),
-            &|m: &Move| match m {
-                Move::Regular(to) => (to.xv(), to.yv(), "".to_string()),
-                Move::Promotion(to, piece_type) => (to.xv(), to.yv(), format!("{:?}", piece_type)),
-            },
+            &|m: &Move| (m.to.xv(), m.to.yv(), format!("{:?}", m.promotion)),
         )
     }
 
+    #[test]
+    fn test_move_flags() {
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+
+        let castling = m(&board, c(4, 7), c(6, 7));
+        assert!(castling.is_castling());
+        assert!(!castling.is_capture());
+
+        let double_push = m(&board, c(0, 6), c(0, 4));
+        assert!(double_push.is_double_push());
+        assert_eq!(double_push.piece, Piece::from_letter('P').unwrap());
+
+        let capture = m(&board, c(4, 3), c(5, 1));
+        assert!(capture.is_capture());
+        assert_eq!(capture.captured, Some(Piece::from_letter('p').unwrap()));
+        assert_eq!(capture.to_string(), "E5F7");
+
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let en_passant = m(&board, c(4, 3), c(3, 2));
+        assert!(en_passant.is_en_passant());
+        assert_eq!(en_passant.captured_coordinate(), c(3, 3));
+        assert_eq!(en_passant.captured, Some(Piece::from_letter('p').unwrap()));
+
+        let board = Board::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let promotion = Move::new(&board, &c(0, 1), &c(0, 0), Some(PieceType::Queen)).unwrap();
+        assert!(promotion.is_promotion());
+        assert_eq!(promotion.to_string(), "A7A8Q");
+    }
+
+    fn move_error(
+        board: &Board,
+        from: Coordinate,
+        to: Coordinate,
+        promotion: Option<PieceType>,
+    ) -> MoveError {
+        match Move::new(board, &from, &to, promotion) {
+            Ok(a_move) => board.apply_move(&a_move).err().unwrap(),
+            Err(err) => err,
+        }
+    }
+
+    #[test]
+    fn test_move_errors() {
+        let board = Board::default();
+        assert_eq!(
+            move_error(&board, c(4, 4), c(4, 3), None),
+            MoveError::EmptySquare(c(4, 4))
+        );
+        assert_eq!(
+            move_error(&board, c(4, 1), c(4, 3), None),
+            MoveError::NotYourPiece(c(4, 1))
+        );
+        assert_eq!(
+            move_error(&board, c(4, 6), c(4, 3), None),
+            MoveError::Unreachable(PieceType::Pawn, c(4, 6), c(4, 3))
+        );
+        assert_eq!(
+            move_error(&board, c(0, 7), c(0, 5), None),
+            MoveError::PathBlocked(c(0, 6))
+        );
+        assert_eq!(
+            move_error(&board, c(1, 7), c(3, 6), None),
+            MoveError::OwnPieceOnTarget(c(3, 6))
+        );
+        assert_eq!(
+            move_error(&board, c(4, 7), c(6, 7), None),
+            MoveError::PathBlocked(c(5, 7))
+        );
+        assert_eq!(
+            move_error(&board, c(4, 6), c(4, 4), Some(PieceType::Queen)),
+            MoveError::UnexpectedPromotion
+        );
+
+        let board = Board::from_fen("4kr2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert_eq!(
+            move_error(&board, c(4, 7), c(6, 7), None),
+            MoveError::CastlingThroughCheck(c(5, 7))
+        );
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1").unwrap();
+        assert_eq!(
+            move_error(&board, c(4, 7), c(6, 7), None),
+            MoveError::CastlingUnavailable(CastlingSide::KingSide)
+        );
+
+        let board = Board::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        assert_eq!(
+            move_error(&board, c(0, 1), c(0, 0), None),
+            MoveError::PromotionRequired
+        );
+        assert_eq!(
+            move_error(&board, c(0, 1), c(0, 0), Some(PieceType::King)),
+            MoveError::InvalidPromotion(PieceType::King)
+        );
+
+        // The pinned bishop would expose the king to the rook
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+        let error = move_error(&board, c(4, 6), c(3, 5), None);
+        assert_eq!(error, MoveError::IsCheck(PieceType::Rook, c(4, 0)));
+        assert_eq!(
+            error.to_string(),
+            "Cannot make this move, the rook on E8 would give check to your king"
+        );
+    }
+
+    pub fn board_from_configuration(configuration: [[char; BOARD_SIZE]; BOARD_SIZE]) -> Board {
+        // Test positions only exercise single pieces, so they skip position validation
+        let tiles =
+            configuration.map(|row| row.map(|letter| TileContent::from_letter(letter).unwrap()));
+        Board::from_tiles(tiles)
+    }
+
     pub fn c(x: usize, y: usize) -> Coordinate {
         Coordinate::try_new(x, y).unwrap()
     }
 
-    pub fn m(x: usize, y: usize) -> Move {
-        Move::Regular(c(x, y))
+    pub fn m(board: &Board, from: Coordinate, to: Coordinate) -> Move {
+        Move::new(board, &from, &to, None).unwrap()
     }
 }
@@ -1,8 +1,7 @@
 use crate::logic::basic::{Coordinate, Player};
 use crate::logic::board::{Board, TileContent, BOARD_SIZE};
-use crate::utils::ValueError;
 
-use super::{Move, MoveError, PieceType};
+use super::{new_move, Move, MoveError, PieceType};
 
 pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
     let mut moves: Vec<Move> = Vec::new();
@@ -15,13 +14,17 @@ pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
     for to in all_moves_regular(board, from) {
         if promotion_y == to.yv() {
             // Requires promotion
-            moves.push(Move::Promotion(to.clone(), PieceType::Queen));
-            moves.push(Move::Promotion(to.clone(), PieceType::Rook));
-            moves.push(Move::Promotion(to.clone(), PieceType::Knight));
-            moves.push(Move::Promotion(to, PieceType::Bishop));
+            for piece_type in [
+                PieceType::Queen,
+                PieceType::Rook,
+                PieceType::Knight,
+                PieceType::Bishop,
+            ] {
+                moves.push(Move::new(board, from, &to, Some(piece_type)).unwrap());
+            }
         } else {
             // Regular move
-            moves.push(Move::Regular(to));
+            moves.push(new_move(board, from, to));
         }
     }
 
@@ -31,14 +34,14 @@ pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
 fn all_moves_regular(board: &Board, from: &Coordinate) -> Vec<Coordinate> {
     let mut moves: Vec<Coordinate> = Vec::new();
 
-    if let Ok(to) = coordinate_up(&board.turn, from, 1) {
+    if let Some(to) = coordinate_up(&board.turn, from, 1) {
         if matches!(board.get_tile(&to), TileContent::Empty) {
             // - Regular move
             moves.push(to.clone());
 
             // - Double move
             if is_player_pawn_original_pos(board, from) {
-                if let Ok(to_double) = coordinate_up(&board.turn, from, 2) {
+                if let Some(to_double) = coordinate_up(&board.turn, from, 2) {
                     if matches!(board.get_tile(&to_double), TileContent::Empty) {
                         moves.push(to_double);
                     }
@@ -81,119 +84,81 @@ fn all_moves_regular(board: &Board, from: &Coordinate) -> Vec<Coordinate> {
     moves
 }
 
-pub fn move_piece(board: &Board, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-    if let Move::Promotion(_, new_type) = a_move {
+pub fn check_move(board: &Board, a_move: &Move) -> Result<(), MoveError> {
+    if let Some(new_type) = &a_move.promotion {
         if !matches!(
             new_type,
             PieceType::Queen | PieceType::Rook | PieceType::Knight | PieceType::Bishop
         ) {
-            return Err(MoveError::IllegalMove);
+            return Err(MoveError::InvalidPromotion(new_type.clone()));
         }
     }
 
-    let to = a_move.get_to();
-    let mut new_board = move_piece_regular(board, from, to)?;
+    let from = &a_move.from;
+    let to = &a_move.to;
+    check_move_regular(board, from, to)?;
 
     let requires_promotion = match board.turn {
         Player::White => 0,
         Player::Black => BOARD_SIZE - 1,
     } == to.yv();
 
-    match a_move {
-        Move::Regular(_) if requires_promotion => Err(MoveError::PromotionRequired),
-        Move::Regular(_) if !requires_promotion => Ok(new_board),
-        Move::Promotion(_, new_type) if requires_promotion => {
-            if let TileContent::Piece(piece) = new_board.get_tile(to) {
-                let new_tile = TileContent::Piece(piece.promoted(new_type.clone()));
-                new_board.set_tile(to, new_tile);
-                Ok(new_board)
-            } else {
-                panic!(
-                    "Illegal state at move {:?} of player {:?} from {}",
-                    a_move, board.turn, from
-                );
-            }
-        }
-        _ => Err(MoveError::IllegalMove),
+    match (&a_move.promotion, requires_promotion) {
+        (None, true) => Err(MoveError::PromotionRequired),
+        (None, false) | (Some(_), true) => Ok(()),
+        (Some(_), false) => Err(MoveError::UnexpectedPromotion),
     }
 }
 
-fn move_piece_regular(
-    board: &Board,
-    from: &Coordinate,
-    to: &Coordinate,
-) -> Result<Board, MoveError> {
+pub fn move_piece(board: &Board, a_move: &Move) -> Result<Board, MoveError> {
+    check_move(board, a_move)?;
+    Ok(board.made(a_move))
+}
+
+fn check_move_regular(board: &Board, from: &Coordinate, to: &Coordinate) -> Result<(), MoveError> {
     // Piece at `from` and `piece` is from player with turn already checked
+    let unreachable = || MoveError::Unreachable(PieceType::Pawn, from.clone(), to.clone());
     let from_x = from.xv();
     let to_x = to.xv();
 
     // Move
     if from_x == to_x {
-        if !matches!(board.get_tile(to), TileContent::Empty) {
-            return Err(MoveError::IllegalMove);
-        }
+        let coord_up = coordinate_up(&board.turn, from, 1).ok_or_else(unreachable)?;
 
-        // - Regular move
-        let regular_move = coordinate_up(&board.turn, from, 1);
+        // - Double move
+        let is_double_move = coord_up != *to
+            // On original position
+            && is_player_pawn_original_pos(board, from)
+            // Move is actually a double move
+            && coordinate_up(&board.turn, from, 2).is_some_and(|coord| &coord == to);
 
-        if let Ok(coord_up) = regular_move {
-            if coord_up == *to {
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                return Ok(new_board);
-            }
+        if coord_up != *to && !is_double_move {
+            return Err(unreachable());
+        }
 
-            // - Double move
-            let is_double_move =
-                // On original position
-                is_player_pawn_original_pos(board, from)
-                // No piece in between
-                && matches!(board.get_tile(&coord_up), TileContent::Empty)
-                // Move is actually a double move
-                && coordinate_up(
-                    &board.turn, from, 2
-                ).map_or(false, |coord| &coord == to);
-
-            if is_double_move {
-                let mut new_board = board.turned();
-                new_board.en_passant = Some(to.clone());
-                new_board.move_tile(from, to);
-                return Ok(new_board);
+        // No piece in between or on the target, pawns cannot capture forward
+        for coord in [coord_up, to.clone()] {
+            if !matches!(board.get_tile(&coord), TileContent::Empty) {
+                return Err(MoveError::PathBlocked(coord));
             }
         }
 
-        return Err(MoveError::IllegalMove);
+        return Ok(());
     }
 
     // Capture
     if is_move_up_diagonal(&board.turn, from, to) {
         return match board.get_tile(to) {
             // - Regular capture
-            TileContent::Piece(piece) => {
-                if piece.player != board.turn {
-                    let mut new_board = board.turned();
-                    new_board.move_tile(from, to);
-                    Ok(new_board)
-                } else {
-                    Err(MoveError::IllegalMove)
-                }
-            }
+            TileContent::Piece(piece) if piece.player != board.turn => Ok(()),
+            TileContent::Piece(_) => Err(MoveError::OwnPieceOnTarget(to.clone())),
             // - En Passant
-            TileContent::Empty => {
-                if is_en_passant(board, to) {
-                    let mut new_board = board.turned();
-                    let en_passant = board.en_passant.as_ref().unwrap();
-                    new_board.clear_tile(en_passant);
-                    new_board.move_tile(from, to);
-                    Ok(new_board)
-                } else {
-                    Err(MoveError::IllegalMove)
-                }
-            }
+            TileContent::Empty if is_en_passant(board, to) => Ok(()),
+            TileContent::Empty => Err(unreachable()),
         };
     }
 
-    Err(MoveError::IllegalMove)
+    Err(unreachable())
 }
 
 fn is_player_pawn_original_pos(board: &Board, coordinate: &Coordinate) -> bool {
@@ -203,33 +168,31 @@ fn is_player_pawn_original_pos(board: &Board, coordinate: &Coordinate) -> bool {
     } == coordinate.yv())
 }
 
-fn coordinate_up(
-    player: &Player,
-    from: &Coordinate,
-    steps: usize,
-) -> Result<Coordinate, ValueError> {
+fn coordinate_up(player: &Player, from: &Coordinate, steps: usize) -> Option<Coordinate> {
+    // `None` once the pawn would leave the board
     let from_x = from.xv();
     let from_y = from.yv();
 
-    match player {
-        Player::White if steps <= from_y => Coordinate::try_new(from_x, from_y - steps),
-        Player::Black => Coordinate::try_new(from_x, from_y + steps),
-        _ => Err(ValueError),
-    }
+    let to_y = match player {
+        Player::White => from_y.checked_sub(steps)?,
+        Player::Black => from_y + steps,
+    };
+
+    Coordinate::try_new(from_x, to_y).ok()
 }
 
 fn is_en_passant(board: &Board, to: &Coordinate) -> bool {
     board
         .en_passant
         .as_ref()
-        .and_then(|coord| coordinate_up(&board.turn, coord, 1).ok())
-        .map_or(false, |target| target == *to)
+        .and_then(|coord| coordinate_up(&board.turn, coord, 1))
+        .is_some_and(|target| target == *to)
 }
 
 fn is_move_up_diagonal(player: &Player, from: &Coordinate, to: &Coordinate) -> bool {
     match coordinate_up(player, from, 1) {
-        Ok(up) => up.yv() == to.yv() && from.xv().abs_diff(to.xv()) == 1,
-        Err(ValueError) => false,
+        Some(up) => up.yv() == to.yv() && from.xv().abs_diff(to.xv()) == 1,
+        None => false,
     }
 }
 
@@ -237,13 +200,15 @@ fn is_move_up_diagonal(player: &Player, from: &Coordinate, to: &Coordinate) -> b
 mod tests {
     use crate::logic::basic::Player;
     use crate::logic::board::{Board, TileContent};
-    use crate::logic::pieces::tests::{assert_all_moves_valid, assert_valid_in_all_moves, c, m};
+    use crate::logic::pieces::tests::{
+        assert_all_moves_valid, assert_valid_in_all_moves, board_from_configuration, c, m,
+    };
     use crate::logic::pieces::{Move, Piece, PieceType};
 
     use super::{all_moves, move_piece};
 
     fn test_board() -> Board {
-        Board::from_configuration([
+        board_from_configuration([
             [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
             [' ', 'p', ' ', ' ', 'P', ' ', ' ', ' '],
             [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
@@ -285,7 +250,7 @@ mod tests {
         );
         // En passant
         {
-            let prepared = move_piece(&board, &c(6, 6), &m(6, 4)).unwrap();
+            let prepared = move_piece(&board, &m(&board, c(6, 6), c(6, 4))).unwrap();
             assert_valid_in_all_moves(
                 &prepared,
                 Piece::from_letter('P').unwrap(),
@@ -303,7 +268,7 @@ mod tests {
         );
         // En passant
         {
-            let prepared = move_piece(&turned, &c(1, 1), &m(1, 3)).unwrap();
+            let prepared = move_piece(&turned, &m(&turned, c(1, 1), c(1, 3))).unwrap();
             assert_valid_in_all_moves(
                 &prepared,
                 Piece::from_letter('p').unwrap(),
@@ -317,41 +282,41 @@ mod tests {
     fn test_regular_moves() {
         let board = test_board();
 
-        move_piece(&board, &c(0, 3), &m(0, 2)).unwrap();
-        move_piece(&board, &c(2, 6), &m(2, 5)).unwrap();
+        move_piece(&board, &m(&board, c(0, 3), c(0, 2))).unwrap();
+        move_piece(&board, &m(&board, c(2, 6), c(2, 5))).unwrap();
         // Cannot return
-        assert!(move_piece(&board, &c(0, 3), &m(0, 4)).is_err());
+        assert!(move_piece(&board, &m(&board, c(0, 3), c(0, 4))).is_err());
         // Cannot move to the side
-        assert!(move_piece(&board, &c(1, 6), &m(0, 6)).is_err());
+        assert!(move_piece(&board, &m(&board, c(1, 6), c(0, 6))).is_err());
         // Cannot move through figures
-        assert!(move_piece(&board, &c(3, 6), &m(3, 5)).is_err());
-        assert!(move_piece(&board, &c(7, 6), &m(7, 5)).is_err());
+        assert!(move_piece(&board, &m(&board, c(3, 6), c(3, 5))).is_err());
+        assert!(move_piece(&board, &m(&board, c(7, 6), c(7, 5))).is_err());
 
         let turned = board.turned();
-        move_piece(&turned, &c(5, 4), &m(5, 5)).unwrap();
+        move_piece(&turned, &m(&turned, c(5, 4), c(5, 5))).unwrap();
         // Cannot return
-        assert!(move_piece(&turned, &c(5, 4), &m(5, 3)).is_err());
+        assert!(move_piece(&turned, &m(&turned, c(5, 4), c(5, 3))).is_err());
         // Cannot move through figures
-        assert!(move_piece(&turned, &c(7, 5), &m(7, 6)).is_err());
+        assert!(move_piece(&turned, &m(&turned, c(7, 5), c(7, 6))).is_err());
     }
 
     #[test]
     fn test_double_moves() {
         let board = test_board();
 
-        move_piece(&board, &c(1, 6), &m(1, 4)).unwrap();
-        assert!(move_piece(&board, &c(0, 3), &m(0, 1)).is_err());
+        move_piece(&board, &m(&board, c(1, 6), c(1, 4))).unwrap();
+        assert!(move_piece(&board, &m(&board, c(0, 3), c(0, 1))).is_err());
 
         let turned = board.turned();
-        move_piece(&turned, &c(1, 1), &m(1, 3)).unwrap();
-        assert!(move_piece(&turned, &c(4, 3), &m(4, 5)).is_err());
+        move_piece(&turned, &m(&turned, c(1, 1), c(1, 3))).unwrap();
+        assert!(move_piece(&turned, &m(&turned, c(4, 3), c(4, 5))).is_err());
     }
 
     #[test]
     fn test_regular_captures() {
         let board = test_board();
 
-        let new_board = move_piece(&board, &c(6, 6), &m(7, 5)).unwrap();
+        let new_board = move_piece(&board, &m(&board, c(6, 6), c(7, 5))).unwrap();
         assert!(matches!(new_board.get_tile(&c(6, 6)), TileContent::Empty));
         assert!(matches!(
             new_board.get_tile(&c(7, 5)),
@@ -363,47 +328,44 @@ mod tests {
         }
 
         // Cannot throw own
-        assert!(move_piece(&board, &c(2, 6), &m(3, 5)).is_err());
+        assert!(move_piece(&board, &m(&board, c(2, 6), c(3, 5))).is_err());
         // Cannot throw outside of diagonal
-        assert!(move_piece(&board, &c(1, 1), &m(2, 4)).is_err());
+        assert!(move_piece(&board, &m(&board, c(1, 1), c(2, 4))).is_err());
     }
 
     #[test]
     fn test_en_passants() {
         let board = test_board();
         {
-            let prepared = move_piece(&board, &c(6, 6), &m(6, 4)).unwrap();
+            let prepared = move_piece(&board, &m(&board, c(6, 6), c(6, 4))).unwrap();
             assert!(!matches!(prepared.get_tile(&c(6, 4)), TileContent::Empty));
-            let new_board = move_piece(&prepared, &c(5, 4), &m(6, 5)).unwrap();
+            let new_board = move_piece(&prepared, &m(&prepared, c(5, 4), c(6, 5))).unwrap();
             assert!(matches!(new_board.get_tile(&c(6, 4)), TileContent::Empty));
         }
-        assert!(move_piece(&board, &c(0, 3), &m(1, 2)).is_err());
+        assert!(move_piece(&board, &m(&board, c(0, 3), c(1, 2))).is_err());
 
         let turned = board.turned();
         {
-            let prepared = move_piece(&turned, &c(1, 1), &m(1, 3)).unwrap();
+            let prepared = move_piece(&turned, &m(&turned, c(1, 1), c(1, 3))).unwrap();
             assert!(!matches!(prepared.get_tile(&c(1, 3)), TileContent::Empty));
-            let new_board = move_piece(&prepared, &c(0, 3), &m(1, 2)).unwrap();
+            let new_board = move_piece(&prepared, &m(&prepared, c(0, 3), c(1, 2))).unwrap();
             assert!(matches!(new_board.get_tile(&c(1, 3)), TileContent::Empty));
         }
-        assert!(move_piece(&turned, &c(5, 4), &m(6, 5)).is_err());
+        assert!(move_piece(&turned, &m(&turned, c(5, 4), c(6, 5))).is_err());
     }
 
     #[test]
     fn test_promotion() {
         let board = test_board();
 
-        assert!(move_piece(&board, &c(4, 1), &m(4, 0)).is_err());
+        assert!(move_piece(&board, &m(&board, c(4, 1), c(4, 0))).is_err());
 
-        assert!(move_piece(&board, &c(4, 1), &Move::Promotion(c(4, 0), PieceType::King)).is_err());
+        let to_king = Move::new(&board, &c(4, 1), &c(4, 0), Some(PieceType::King)).unwrap();
+        assert!(move_piece(&board, &to_king).is_err());
 
         {
-            let result = move_piece(
-                &board,
-                &c(4, 1),
-                &Move::Promotion(c(4, 0), PieceType::Queen),
-            )
-            .unwrap();
+            let to_queen = Move::new(&board, &c(4, 1), &c(4, 0), Some(PieceType::Queen)).unwrap();
+            let result = move_piece(&board, &to_queen).unwrap();
 
             if let TileContent::Piece(piece) = result.get_tile(&c(4, 0)) {
                 assert!(matches!(piece.piece_type, PieceType::Queen));
@@ -1,46 +1,35 @@
 use crate::logic::basic::Coordinate;
+use crate::logic::bitboard;
 use crate::logic::board::Board;
 
-use super::queen::{all_moves_straight, is_straight, piece_between_straight};
-use super::{is_friendly_fire, Move, MoveError};
+use super::queen::all_moves_straight;
+use super::{check_regular_move, Move, MoveError};
 
 pub fn all_moves(board: &Board, from: &Coordinate) -> Vec<Move> {
     all_moves_straight(board, from)
 }
 
-pub fn move_piece(board: &Board, from: &Coordinate, a_move: &Move) -> Result<Board, MoveError> {
-    match a_move {
-        Move::Promotion(..) => Err(MoveError::IllegalMove),
-        Move::Regular(to) => {
-            if from == to {
-                return Err(MoveError::IllegalMove);
-            }
-
-            if is_friendly_fire(board, to) {
-                return Err(MoveError::IllegalMove);
-            }
+pub fn check_move(board: &Board, a_move: &Move) -> Result<(), MoveError> {
+    check_regular_move(board, a_move, bitboard::rook_attacks)
+}
 
-            if is_straight(from, to) && !piece_between_straight(board, from, to) {
-                let mut new_board = board.turned();
-                new_board.move_tile(from, to);
-                Ok(new_board)
-            } else {
-                Err(MoveError::IllegalMove)
-            }
-        }
-    }
+pub fn move_piece(board: &Board, a_move: &Move) -> Result<Board, MoveError> {
+    check_move(board, a_move)?;
+    Ok(board.made(a_move))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::logic::board::Board;
-    use crate::logic::pieces::tests::{assert_all_moves_valid, assert_valid_in_all_moves};
+    use crate::logic::pieces::tests::{
+        assert_all_moves_valid, assert_valid_in_all_moves, board_from_configuration,
+    };
     use crate::logic::pieces::Piece;
 
     use super::{all_moves, move_piece};
 
     fn test_board() -> Board {
-        Board::from_configuration([
+        board_from_configuration([
             [' ', ' ', ' ', ' ', ' ', ' ', ' ', ' '],
             [' ', 'p', ' ', ' ', 'P', ' ', 'r', ' '],
             [' ', ' ', 'q', ' ', ' ', ' ', ' ', ' '],
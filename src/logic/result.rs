@@ -0,0 +1,111 @@
+use crate::logic::basic::Player;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+impl Outcome {
+    pub fn win(player: &Player) -> Self {
+        match player {
+            Player::White => Self::WhiteWins,
+            Player::Black => Self::BlackWins,
+        }
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            Self::WhiteWins => Some(Player::White),
+            Self::BlackWins => Some(Player::Black),
+            Self::Draw => None,
+        }
+    }
+
+    pub fn to_token(&self) -> &str {
+        match self {
+            Self::WhiteWins => "1-0",
+            Self::BlackWins => "0-1",
+            Self::Draw => "1/2-1/2",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Termination {
+    Checkmate,
+    Resignation,
+    Timeout,
+    Stalemate,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    Agreement,
+    // A recorded result without a known reason, with the PGN Termination tag if there was one
+    Unknown(Option<String>),
+}
+
+impl Termination {
+    pub fn to_label(&self) -> &str {
+        match self {
+            Self::Checkmate => "checkmate",
+            Self::Resignation => "resignation",
+            Self::Timeout => "timeout",
+            Self::Stalemate => "stalemate",
+            Self::InsufficientMaterial => "insufficient material",
+            Self::ThreefoldRepetition => "threefold repetition",
+            Self::FivefoldRepetition => "fivefold repetition",
+            Self::FiftyMoveRule => "fifty-move rule",
+            Self::SeventyFiveMoveRule => "seventy-five-move rule",
+            Self::Agreement => "agreement",
+            Self::Unknown(Some(reason)) => reason,
+            Self::Unknown(None) => "unknown reason",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            Self::Checkmate,
+            Self::Resignation,
+            Self::Timeout,
+            Self::Stalemate,
+            Self::InsufficientMaterial,
+            Self::ThreefoldRepetition,
+            Self::FivefoldRepetition,
+            Self::FiftyMoveRule,
+            Self::SeventyFiveMoveRule,
+            Self::Agreement,
+        ]
+        .into_iter()
+        .find(|termination| termination.to_label() == label)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub termination: Termination,
+}
+
+impl GameResult {
+    pub fn win(player: &Player, termination: Termination) -> Self {
+        Self {
+            outcome: Outcome::win(player),
+            termination,
+        }
+    }
+
+    pub fn draw(termination: Termination) -> Self {
+        Self {
+            outcome: Outcome::Draw,
+            termination,
+        }
+    }
+
+    pub fn winner(&self) -> Option<Player> {
+        self.outcome.winner()
+    }
+}
@@ -0,0 +1,434 @@
+use crate::logic::basic::{column_to_name, name_to_column, name_to_row, row_to_name, Coordinate};
+use crate::logic::board::Board;
+use crate::logic::castling::CastlingSide;
+use crate::logic::pieces::{Move, PieceType};
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SanError {
+    InvalidSyntax(String),
+    IllegalMove(String),
+    AmbiguousMove(String, Vec<String>),
+    MissingPromotion(Coordinate, Coordinate),
+}
+
+impl Error for SanError {}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax(text) => write!(f, "\"{}\" is not a move", text),
+            Self::IllegalMove(text) => write!(f, "{} is not a legal move", text),
+            Self::AmbiguousMove(text, candidates) => write!(
+                f,
+                "{} is ambiguous, it could be {}",
+                text,
+                candidates.join(" or ")
+            ),
+            Self::MissingPromotion(_, to) => {
+                let to = to.to_field_name().to_lowercase();
+                write!(f, "Moving to {} needs a promotion piece, e.g. {}=Q", to, to)
+            }
+        }
+    }
+}
+
+// What a move in SAN or coordinate notation says about the move it stands for
+struct MovePattern {
+    piece_type: Option<PieceType>,
+    from_x: Option<usize>,
+    from_y: Option<usize>,
+    to: Coordinate,
+    promotion: Option<PieceType>,
+}
+
+impl MovePattern {
+    fn parse(notation: &str) -> Option<Self> {
+        let mut chars: Vec<char> = notation
+            .chars()
+            .filter(|c| !matches!(c, 'x' | '-'))
+            .collect();
+
+        // Uppercase is a piece letter except in coordinate moves like B1C3
+        let piece_type = match chars.first() {
+            Some(&letter) if "KQRBN".contains(letter) && !is_coordinate_move(&chars) => {
+                chars.remove(0);
+                Some(PieceType::from_letter(letter).ok()?)
+            }
+            _ => None,
+        };
+
+        // Promotions are written as e8=Q or, in coordinate moves, e7e8q
+        let promotion = match chars.last() {
+            Some(letter) if "QRBNqrbn".contains(*letter) && chars.len() > 2 => {
+                let letter = chars.pop()?.to_ascii_uppercase();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(PieceType::from_letter(letter).ok()?)
+            }
+            _ => None,
+        };
+
+        let (to_column, to_row) = match chars.len() {
+            2..=4 => (chars[chars.len() - 2], chars[chars.len() - 1]),
+            _ => return None,
+        };
+        let to =
+            Coordinate::try_new(name_to_column(to_column).ok()?, name_to_row(to_row).ok()?).ok()?;
+
+        let mut from_x = None;
+        let mut from_y = None;
+        for &c in &chars[..chars.len() - 2] {
+            match (name_to_column(c), name_to_row(c)) {
+                (Ok(x), _) if from_x.is_none() && from_y.is_none() => from_x = Some(x),
+                (_, Ok(y)) if from_y.is_none() => from_y = Some(y),
+                _ => return None,
+            }
+        }
+
+        Some(Self {
+            piece_type,
+            from_x,
+            from_y,
+            to,
+            promotion,
+        })
+    }
+
+    fn matches(&self, a_move: &Move) -> bool {
+        // Without a piece letter it is a pawn move, unless the square it starts from is given
+        let piece_type = match (&self.piece_type, self.from_x, self.from_y) {
+            (Some(piece_type), ..) => Some(piece_type),
+            (None, Some(_), Some(_)) => None,
+            (None, ..) => Some(&PieceType::Pawn),
+        };
+
+        a_move.to == self.to
+            && piece_type.is_none_or(|piece_type| a_move.piece.piece_type == *piece_type)
+            && self.from_x.is_none_or(|x| a_move.from.xv() == x)
+            && self.from_y.is_none_or(|y| a_move.from.yv() == y)
+            && (self.promotion.is_none() || a_move.promotion == self.promotion)
+    }
+}
+
+fn is_coordinate_move(chars: &[char]) -> bool {
+    chars.len() >= 4
+        && chars[..4]
+            .chunks(2)
+            .all(|square| name_to_column(square[0]).is_ok() && name_to_row(square[1]).is_ok())
+}
+
+impl Board {
+    pub fn parse_san(&self, text: &str) -> Result<Move, SanError> {
+        // Accepts SAN like Nf3, exd5, O-O or e8=Q as well as coordinates like e2e4 or e7e8q
+        let notation = text.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        let candidates: Vec<Move> = match notation {
+            "O-O" | "0-0" => self.castling_moves(CastlingSide::KingSide),
+            "O-O-O" | "0-0-0" => self.castling_moves(CastlingSide::QueenSide),
+            _ => {
+                let pattern = MovePattern::parse(notation)
+                    .ok_or_else(|| SanError::InvalidSyntax(text.to_string()))?;
+                self.legal_moves()
+                    .into_iter()
+                    .filter(|a_move| pattern.matches(a_move))
+                    .collect()
+            }
+        };
+
+        match candidates.as_slice() {
+            [] => Err(SanError::IllegalMove(text.to_string())),
+            [a_move] => Ok(a_move.clone()),
+            // Only the promotion piece is left open
+            [first, ..]
+                if candidates
+                    .iter()
+                    .all(|a_move| a_move.from == first.from && a_move.to == first.to) =>
+            {
+                Err(SanError::MissingPromotion(
+                    first.from.clone(),
+                    first.to.clone(),
+                ))
+            }
+            _ => Err(SanError::AmbiguousMove(
+                text.to_string(),
+                candidates
+                    .iter()
+                    .map(|a_move| self.to_san(a_move))
+                    .collect(),
+            )),
+        }
+    }
+
+    fn castling_moves(&self, side: CastlingSide) -> Vec<Move> {
+        self.legal_moves()
+            .into_iter()
+            .filter(|a_move| {
+                a_move.is_castling()
+                    && CastlingSide::from_king_target(&a_move.from, &a_move.to) == side
+            })
+            .collect()
+    }
+
+    pub fn to_san(&self, a_move: &Move) -> String {
+        // The move is expected to be legal in this position
+        let mut san = self.san_without_suffix(a_move);
+
+        let after = self.made(a_move);
+        if after.is_current_player_checkmate() {
+            san.push('#');
+        } else if after.is_player_on_check(&after.turn) {
+            san.push('+');
+        }
+
+        san
+    }
+
+    pub fn to_san_line(&self, moves: &[Move]) -> Vec<String> {
+        // Each move is written for the position it is played in
+        let mut board = self.clone();
+
+        moves
+            .iter()
+            .map(|a_move| {
+                let san = board.to_san(a_move);
+                board.make_move(a_move);
+                san
+            })
+            .collect()
+    }
+
+    fn san_without_suffix(&self, a_move: &Move) -> String {
+        if a_move.is_castling() {
+            return match CastlingSide::from_king_target(&a_move.from, &a_move.to) {
+                CastlingSide::KingSide => "O-O".to_string(),
+                CastlingSide::QueenSide => "O-O-O".to_string(),
+            };
+        }
+
+        let mut san = String::new();
+
+        match a_move.piece.piece_type {
+            // Pawn captures name the file the pawn came from instead of a piece letter
+            PieceType::Pawn if a_move.is_capture() => san.push_str(&file_name(a_move.from.xv())),
+            PieceType::Pawn => {}
+            ref piece_type => {
+                san.push(piece_type.to_letter());
+                san.push_str(&self.disambiguation(a_move));
+            }
+        }
+
+        if a_move.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&a_move.to.to_field_name().to_lowercase());
+
+        if let Some(piece_type) = &a_move.promotion {
+            san.push('=');
+            san.push(piece_type.to_letter());
+        }
+
+        san
+    }
+
+    fn disambiguation(&self, a_move: &Move) -> String {
+        // Other pieces of the same type that could go to the same square
+        let rivals: Vec<Move> = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| {
+                other.to == a_move.to && other.from != a_move.from && other.piece == a_move.piece
+            })
+            .collect();
+
+        if rivals.is_empty() {
+            "".to_string()
+        } else if rivals
+            .iter()
+            .all(|other| other.from.xv() != a_move.from.xv())
+        {
+            file_name(a_move.from.xv())
+        } else if rivals
+            .iter()
+            .all(|other| other.from.yv() != a_move.from.yv())
+        {
+            row_to_name(a_move.from.yv())
+        } else {
+            a_move.from.to_field_name().to_lowercase()
+        }
+    }
+}
+
+fn file_name(x: usize) -> String {
+    column_to_name(x).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::basic::Coordinate;
+    use crate::logic::board::Board;
+    use crate::logic::pieces::{Move, PieceType};
+
+    use super::SanError;
+
+    fn c(name: &str) -> Coordinate {
+        Coordinate::try_from_field_name(name).unwrap()
+    }
+
+    fn parsed(fen: &str, text: &str) -> Result<(String, String), SanError> {
+        let board = Board::from_fen(fen).unwrap();
+        board.parse_san(text).map(|a_move| {
+            (
+                a_move.from.to_field_name().to_lowercase(),
+                a_move.to.to_field_name().to_lowercase(),
+            )
+        })
+    }
+
+    fn san(fen: &str, from: &str, to: &str, promotion: Option<PieceType>) -> String {
+        let board = Board::from_fen(fen).unwrap();
+        let from = Coordinate::try_from_field_name(from).unwrap();
+        let to = Coordinate::try_from_field_name(to).unwrap();
+        let a_move = Move::new(&board, &from, &to, promotion).unwrap();
+
+        assert!(board.is_legal_move(&a_move));
+        board.to_san(&a_move)
+    }
+
+    #[test]
+    fn test_san_pieces_and_pawns() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(san(start, "e2", "e4", None), "e4");
+        assert_eq!(san(start, "g1", "f3", None), "Nf3");
+
+        let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+        assert_eq!(san(fen, "e4", "d5", None), "exd5");
+        assert_eq!(san(fen, "f1", "b5", None), "Bb5+");
+
+        let en_passant = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        assert_eq!(san(en_passant, "e5", "d6", None), "exd6");
+    }
+
+    #[test]
+    fn test_san_castling_and_promotion() {
+        let castling = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(san(castling, "e1", "g1", None), "O-O");
+        assert_eq!(san(castling, "e1", "c1", None), "O-O-O");
+
+        let promotion = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        assert_eq!(san(promotion, "a7", "a8", Some(PieceType::Queen)), "a8=Q");
+        assert_eq!(
+            san(promotion, "a7", "b8", Some(PieceType::Knight)),
+            "axb8=N"
+        );
+    }
+
+    #[test]
+    fn test_san_disambiguation() {
+        // Knights on b1 and f1, rooks on a1 and a5, queens on d3, h3 and h7
+        let fen = "4k3/7Q/8/R7/8/3Q3Q/8/RN3NK1 w - - 0 1";
+        assert_eq!(san(fen, "b1", "d2", None), "Nbd2");
+        assert_eq!(san(fen, "a1", "a3", None), "R1a3");
+        assert_eq!(san(fen, "a5", "a3", None), "R5a3");
+        assert_eq!(san(fen, "h3", "f5", None), "Qh3f5");
+        assert_eq!(san(fen, "d3", "e4", None), "Qde4+");
+    }
+
+    #[test]
+    fn test_san_checkmate_and_line() {
+        let fen = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+        assert_eq!(san(fen, "a1", "a8", None), "Ra8#");
+
+        let board = Board::default();
+        let mut line = Vec::new();
+        let mut position = board.clone();
+        for (from, to) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+            let from = Coordinate::try_from_field_name(from).unwrap();
+            let to = Coordinate::try_from_field_name(to).unwrap();
+            let a_move = Move::new(&position, &from, &to, None).unwrap();
+            position.make_move(&a_move);
+            line.push(a_move);
+        }
+        assert_eq!(board.to_san_line(&line), ["f3", "e5", "g4", "Qh4#"]);
+    }
+
+    #[test]
+    fn test_parse_san() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let ok = |from: &str, to: &str| Ok((from.to_string(), to.to_string()));
+        assert_eq!(parsed(start, "e4"), ok("e2", "e4"));
+        assert_eq!(parsed(start, "Nf3"), ok("g1", "f3"));
+        assert_eq!(parsed(start, "e2e4"), ok("e2", "e4"));
+        assert_eq!(parsed(start, "G1F3"), ok("g1", "f3"));
+        assert_eq!(parsed(start, "Ng1-f3"), ok("g1", "f3"));
+
+        let fen = "4k3/7Q/8/R7/8/3Q3Q/8/RN3NK1 w - - 0 1";
+        assert_eq!(parsed(fen, "Nbd2"), ok("b1", "d2"));
+        assert_eq!(parsed(fen, "R5a3"), ok("a5", "a3"));
+        assert_eq!(parsed(fen, "Qh3xf5+"), ok("h3", "f5"));
+        assert_eq!(
+            parsed(fen, "Nd2"),
+            Err(SanError::AmbiguousMove(
+                "Nd2".to_string(),
+                vec!["Nbd2".to_string(), "Nfd2".to_string()]
+            ))
+        );
+
+        let castling = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(parsed(castling, "O-O"), ok("e1", "g1"));
+        assert_eq!(parsed(castling, "0-0-0"), ok("e1", "c1"));
+        assert_eq!(parsed(castling, "e1g1"), ok("e1", "g1"));
+    }
+
+    #[test]
+    fn test_parse_san_errors() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(
+            parsed(start, "e5"),
+            Err(SanError::IllegalMove("e5".to_string()))
+        );
+        assert_eq!(
+            parsed(start, "O-O"),
+            Err(SanError::IllegalMove("O-O".to_string()))
+        );
+        for text in ["", "Nf", "Zf3", "e2e4e5", "hello"] {
+            assert_eq!(
+                parsed(start, text),
+                Err(SanError::InvalidSyntax(text.to_string()))
+            );
+        }
+
+        let promotion = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        let board = Board::from_fen(promotion).unwrap();
+        assert_eq!(
+            board.parse_san("a8"),
+            Err(SanError::MissingPromotion(c("a7"), c("a8")))
+        );
+        assert_eq!(
+            board.parse_san("axb8=N").unwrap().promotion,
+            Some(PieceType::Knight)
+        );
+        assert_eq!(
+            board.parse_san("a7a8q").unwrap().promotion,
+            Some(PieceType::Queen)
+        );
+    }
+
+    #[test]
+    fn test_san_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/7Q/8/R7/8/3Q3Q/8/RN3NK1 w - - 0 1",
+            "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            for a_move in board.legal_moves() {
+                assert_eq!(board.parse_san(&board.to_san(&a_move)), Ok(a_move));
+            }
+        }
+    }
+}
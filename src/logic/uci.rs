@@ -0,0 +1,170 @@
+use crate::logic::basic::Coordinate;
+use crate::logic::board::{Board, TileContent};
+use crate::logic::castling::CastlingSide;
+use crate::logic::pieces::{Move, MoveError, PieceType};
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UciError {
+    InvalidSyntax(String),
+    IllegalMove(String, MoveError),
+}
+
+impl Error for UciError {}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax(text) => {
+                write!(f, "\"{}\" is not a UCI move like e2e4 or e7e8q", text)
+            }
+            Self::IllegalMove(text, err) => write!(f, "{} is not legal: {}", text, err),
+        }
+    }
+}
+
+impl Move {
+    pub fn to_uci(&self) -> String {
+        // Castling is written as the king's move, e.g. e1g1
+        let mut uci = format!(
+            "{}{}",
+            self.from.to_field_name().to_lowercase(),
+            self.to.to_field_name().to_lowercase()
+        );
+
+        if let Some(piece_type) = &self.promotion {
+            uci.push(piece_type.to_letter().to_ascii_lowercase());
+        }
+
+        uci
+    }
+
+    pub fn from_uci(board: &Board, text: &str) -> Result<Self, UciError> {
+        let error = || UciError::InvalidSyntax(text.to_string());
+
+        if !text.is_ascii() || !(4..=5).contains(&text.len()) {
+            return Err(error());
+        }
+
+        let from = Coordinate::try_from_field_name(&text[0..2]).map_err(|_| error())?;
+        let to = Coordinate::try_from_field_name(&text[2..4]).map_err(|_| error())?;
+        let promotion = match text[4..].chars().next() {
+            Some(letter @ ('q' | 'r' | 'b' | 'n')) => {
+                Some(PieceType::from_letter(letter.to_ascii_uppercase()).map_err(|_| error())?)
+            }
+            Some(_) => return Err(error()),
+            None => None,
+        };
+
+        let to = castling_target(board, &from, &to).unwrap_or(to);
+        let illegal = |err| UciError::IllegalMove(text.to_string(), err);
+        let a_move = Move::new(board, &from, &to, promotion).map_err(illegal)?;
+
+        board.apply_move(&a_move).map_err(illegal)?;
+        Ok(a_move)
+    }
+}
+
+fn castling_target(board: &Board, from: &Coordinate, to: &Coordinate) -> Option<Coordinate> {
+    // Some tools send castling as the king taking its own rook, e.g. e1h1
+    let (king, rook) = match (board.get_tile(from), board.get_tile(to)) {
+        (TileContent::Piece(king), TileContent::Piece(rook)) => (king, rook),
+        _ => return None,
+    };
+
+    if king.piece_type != PieceType::King
+        || rook.piece_type != PieceType::Rook
+        || king.player != rook.player
+    {
+        return None;
+    }
+
+    let side = CastlingSide::all()
+        .into_iter()
+        .find(|side| side.rook_home(&king.player) == *to)?;
+    let king_x = match side {
+        CastlingSide::KingSide => 6,
+        CastlingSide::QueenSide => 2,
+    };
+
+    Coordinate::try_new(king_x, from.yv()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::board::Board;
+    use crate::logic::pieces::{Move, MoveError, PieceType};
+
+    use super::UciError;
+
+    fn uci(fen: &str, text: &str) -> Result<String, UciError> {
+        let board = Board::from_fen(fen).unwrap();
+        Move::from_uci(&board, text).map(|a_move| board.to_san(&a_move))
+    }
+
+    #[test]
+    fn test_from_uci() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(uci(start, "e2e4"), Ok("e4".to_string()));
+        assert_eq!(uci(start, "g1f3"), Ok("Nf3".to_string()));
+
+        let castling = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(uci(castling, "e1g1"), Ok("O-O".to_string()));
+        assert_eq!(uci(castling, "e1c1"), Ok("O-O-O".to_string()));
+        assert_eq!(uci(castling, "e1h1"), Ok("O-O".to_string()));
+        assert_eq!(uci(castling, "e1a1"), Ok("O-O-O".to_string()));
+
+        let promotion = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        assert_eq!(uci(promotion, "a7a8q"), Ok("a8=Q".to_string()));
+        assert_eq!(uci(promotion, "a7b8n"), Ok("axb8=N".to_string()));
+    }
+
+    #[test]
+    fn test_from_uci_errors() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        for text in ["", "e2", "e2e4e", "e2e9", "i2i4", "e2e4k", "0000", "é2e4"] {
+            assert_eq!(
+                uci(start, text),
+                Err(UciError::InvalidSyntax(text.to_string()))
+            );
+        }
+        assert!(matches!(
+            uci(start, "e3e4"),
+            Err(UciError::IllegalMove(_, MoveError::EmptySquare(_)))
+        ));
+        assert!(matches!(
+            uci(start, "e2e5"),
+            Err(UciError::IllegalMove(_, _))
+        ));
+
+        let promotion = "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        assert_eq!(
+            uci(promotion, "a7a8"),
+            Err(UciError::IllegalMove(
+                "a7a8".to_string(),
+                MoveError::PromotionRequired
+            ))
+        );
+    }
+
+    #[test]
+    fn test_uci_round_trip() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            for a_move in board.legal_moves() {
+                assert_eq!(Move::from_uci(&board, &a_move.to_uci()), Ok(a_move));
+            }
+        }
+
+        let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let a_move = Move::from_uci(&board, "a7b8r").unwrap();
+        assert_eq!(a_move.promotion, Some(PieceType::Rook));
+        assert_eq!(a_move.to_uci(), "a7b8r");
+    }
+}
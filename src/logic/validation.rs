@@ -0,0 +1,250 @@
+use crate::logic::basic::{Coordinate, Player};
+use crate::logic::board::{Board, TileContent, BOARD_MAX_AXIS, BOARD_SIZE};
+use crate::logic::castling::{CastlingRights, CastlingSide};
+use crate::logic::pieces::{Piece, PieceType};
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PositionError {
+    InvalidPiece(char),
+    MissingKing(Player),
+    TooManyKings(Player, usize),
+    PawnOnBackRank(Coordinate),
+    OpponentInCheck(Player),
+    InvalidEnPassant(Coordinate),
+    InvalidCastling(Player, CastlingSide),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidPiece(letter) => write!(f, "'{}' is not a valid piece letter", letter),
+            Self::MissingKing(player) => write!(f, "{} has no king", player.to_label()),
+            Self::TooManyKings(player, count) => {
+                write!(f, "{} has {} kings", player.to_label(), count)
+            }
+            Self::PawnOnBackRank(coordinate) => {
+                write!(f, "Pawn on back rank at {}", coordinate.to_field_name())
+            }
+            Self::OpponentInCheck(player) => write!(
+                f,
+                "{} is in check although it is not their turn",
+                player.to_label()
+            ),
+            Self::InvalidEnPassant(coordinate) => write!(
+                f,
+                "Pawn on {} cannot have just made a double move",
+                coordinate.to_field_name()
+            ),
+            Self::InvalidCastling(player, side) => write!(
+                f,
+                "{} cannot castle {}, king or rook is not on its home square",
+                player.to_label(),
+                side.to_label()
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidPosition(pub Vec<PositionError>);
+
+impl Error for InvalidPosition {}
+
+impl fmt::Display for InvalidPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages: Vec<String> = self.0.iter().map(|error| error.to_string()).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl Board {
+    pub fn validate(&self) -> Result<(), InvalidPosition> {
+        let mut errors = Vec::new();
+
+        validate_kings(self, &mut errors);
+        validate_pawns(self, &mut errors);
+        validate_en_passant(self, &mut errors);
+        validate_castling(self, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidPosition(errors))
+        }
+    }
+}
+
+fn validate_kings(board: &Board, errors: &mut Vec<PositionError>) {
+    for player in [Player::White, Player::Black] {
+        let count = count_pieces(board, &player, PieceType::King);
+
+        match count {
+            0 => errors.push(PositionError::MissingKing(player)),
+            1 => {
+                // The side that just moved cannot have left its king in check
+                if player != board.turn && board.is_player_on_check(&player) {
+                    errors.push(PositionError::OpponentInCheck(player));
+                }
+            }
+            _ => errors.push(PositionError::TooManyKings(player, count)),
+        }
+    }
+}
+
+fn validate_pawns(board: &Board, errors: &mut Vec<PositionError>) {
+    for y in [0, BOARD_MAX_AXIS] {
+        for x in 0..BOARD_SIZE {
+            let coordinate = Coordinate::try_new(x, y).unwrap();
+
+            if let TileContent::Piece(piece) = board.get_tile(&coordinate) {
+                if piece.piece_type == PieceType::Pawn {
+                    errors.push(PositionError::PawnOnBackRank(coordinate));
+                }
+            }
+        }
+    }
+}
+
+fn validate_en_passant(board: &Board, errors: &mut Vec<PositionError>) {
+    let pawn = match &board.en_passant {
+        Some(pawn) => pawn,
+        None => return,
+    };
+
+    // The opponent's pawn moved two squares forward, so both squares it passed are empty
+    let (pawn_y, passed_y, origin_y) = match board.turn {
+        Player::White => (3, 2, 1),
+        Player::Black => (BOARD_SIZE - 4, BOARD_SIZE - 3, BOARD_SIZE - 2),
+    };
+    let opponent_pawn = TileContent::Piece(Piece {
+        player: board.turn.other(),
+        piece_type: PieceType::Pawn,
+    });
+    let is_empty = |y| {
+        let coordinate = Coordinate::try_new(pawn.xv(), y).unwrap();
+        board.get_tile(&coordinate) == &TileContent::Empty
+    };
+
+    if pawn.yv() != pawn_y
+        || board.get_tile(pawn) != &opponent_pawn
+        || !is_empty(passed_y)
+        || !is_empty(origin_y)
+    {
+        errors.push(PositionError::InvalidEnPassant(pawn.clone()));
+    }
+}
+
+fn validate_castling(board: &Board, errors: &mut Vec<PositionError>) {
+    // A right is only possible while king and rook are still on their home squares
+    let possible = CastlingRights::from_placement(board.tiles());
+
+    for player in [Player::White, Player::Black] {
+        for side in CastlingSide::all() {
+            if board.castling.has(&player, &side) && !possible.has(&player, &side) {
+                errors.push(PositionError::InvalidCastling(player.clone(), side));
+            }
+        }
+    }
+}
+
+fn count_pieces(board: &Board, player: &Player, piece_type: PieceType) -> usize {
+    board.bitboards().pieces(player, &piece_type).count_ones() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::basic::Player;
+    use crate::logic::board::Board;
+    use crate::logic::castling::CastlingSide;
+    use crate::logic::fen::FenError;
+    use crate::logic::pieces::tests::c;
+
+    use super::{InvalidPosition, PositionError};
+
+    fn errors(fen: &str) -> Vec<PositionError> {
+        match Board::from_fen(fen) {
+            Err(FenError::InvalidPosition(InvalidPosition(errors))) => errors,
+            other => panic!(
+                "Expected an invalid position for {}, got {:?}",
+                fen,
+                other.err()
+            ),
+        }
+    }
+
+    #[test]
+    fn test_valid_positions() {
+        assert!(Board::default().validate().is_ok());
+        Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    }
+
+    #[test]
+    fn test_kings() {
+        assert_eq!(
+            errors("8/8/8/8/8/8/8/8 w - - 0 1"),
+            vec![
+                PositionError::MissingKing(Player::White),
+                PositionError::MissingKing(Player::Black),
+            ]
+        );
+        assert_eq!(
+            errors("k6k/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![PositionError::TooManyKings(Player::Black, 2)]
+        );
+        assert_eq!(
+            errors("4k2R/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![PositionError::OpponentInCheck(Player::Black)]
+        );
+        // Being in check on your own turn is fine
+        Board::from_fen("4k2R/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    }
+
+    #[test]
+    fn test_all_problems_reported() {
+        assert_eq!(
+            errors("P3k3/8/8/8/8/8/8/R5Kp b Q e3 0 1"),
+            vec![
+                PositionError::PawnOnBackRank(c(0, 0)),
+                PositionError::PawnOnBackRank(c(7, 7)),
+                PositionError::InvalidEnPassant(c(4, 4)),
+                PositionError::InvalidCastling(Player::White, CastlingSide::QueenSide),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_en_passant() {
+        // Pawn of the wrong player
+        assert_eq!(
+            errors("4k3/8/8/3Pp3/8/8/8/4K3 w - d6 0 1"),
+            vec![PositionError::InvalidEnPassant(c(3, 3))]
+        );
+        // Pawn could not have passed the blocked square
+        assert_eq!(
+            errors("4k3/8/3n4/3pP3/8/8/8/4K3 w - d6 0 1"),
+            vec![PositionError::InvalidEnPassant(c(3, 3))]
+        );
+    }
+
+    #[test]
+    fn test_configuration() {
+        let mut configuration = [[' '; 8]; 8];
+        configuration[0][4] = 'k';
+        configuration[7][4] = 'K';
+        assert!(Board::from_configuration(configuration).is_ok());
+
+        configuration[3][3] = 'x';
+        configuration[7][4] = ' ';
+        assert_eq!(
+            Board::from_configuration(configuration).err(),
+            Some(InvalidPosition(vec![
+                PositionError::InvalidPiece('x'),
+                PositionError::MissingKing(Player::White),
+            ]))
+        );
+    }
+}
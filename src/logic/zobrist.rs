@@ -0,0 +1,73 @@
+use crate::logic::basic::{Coordinate, Player};
+use crate::logic::bitboard::{piece_type_index, player_index, square};
+use crate::logic::board::BOARD_SIZE;
+use crate::logic::castling::{CastlingRights, CastlingSide};
+use crate::logic::pieces::Piece;
+
+pub type ZobristKey = u64;
+
+const SQUARES: usize = BOARD_SIZE * BOARD_SIZE;
+
+const PIECE_KEYS: [ZobristKey; 2 * 6 * SQUARES] = keys(0x5EED_0001);
+const CASTLING_KEYS: [ZobristKey; 4] = keys(0x5EED_0002);
+const EN_PASSANT_KEYS: [ZobristKey; BOARD_SIZE] = keys(0x5EED_0003);
+const BLACK_TO_MOVE_KEY: ZobristKey = keys::<1>(0x5EED_0004)[0];
+
+pub fn piece(coordinate: &Coordinate, piece: &Piece) -> ZobristKey {
+    let index = (player_index(&piece.player) * 6 + piece_type_index(&piece.piece_type)) * SQUARES
+        + square(coordinate);
+    PIECE_KEYS[index]
+}
+
+pub fn turn(player: &Player) -> ZobristKey {
+    match player {
+        Player::White => 0,
+        Player::Black => BLACK_TO_MOVE_KEY,
+    }
+}
+
+pub fn castling(rights: &CastlingRights) -> ZobristKey {
+    let mut key = 0;
+
+    for (i, (player, side)) in [
+        (Player::White, CastlingSide::KingSide),
+        (Player::White, CastlingSide::QueenSide),
+        (Player::Black, CastlingSide::KingSide),
+        (Player::Black, CastlingSide::QueenSide),
+    ]
+    .iter()
+    .enumerate()
+    {
+        if rights.has(player, side) {
+            key ^= CASTLING_KEYS[i];
+        }
+    }
+
+    key
+}
+
+pub fn en_passant(pawn: Option<&Coordinate>) -> ZobristKey {
+    // Only the file matters, the rank follows from the side to move
+    match pawn {
+        Some(coordinate) => EN_PASSANT_KEYS[coordinate.xv()],
+        None => 0,
+    }
+}
+
+const fn keys<const N: usize>(seed: u64) -> [ZobristKey; N] {
+    // SplitMix64, fixed seeds keep hashes stable between runs
+    let mut keys = [0; N];
+    let mut state = seed;
+    let mut i = 0;
+
+    while i < N {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+
+    keys
+}
@@ -1,12 +1,203 @@
-pub mod draw;
-pub mod logic;
-pub mod utils;
+mod cli;
 
-use crate::draw::game_render::GameRenderer;
-use crate::logic::game::Game;
+use cli::{Command, USAGE};
+#[cfg(feature = "tui")]
+use cli::{OpponentKind, PlayOptions};
+#[cfg(feature = "tui")]
+use rust_chess::draw::game_render::GameRenderer;
+#[cfg(feature = "tui")]
+use rust_chess::logic::opponent::{Opponent, RandomMover};
+use rust_chess::logic::perft::divide;
+use rust_chess::logic::pgn::read_pgn;
+use rust_chess::{Board, Game, Player};
+
+use std::env;
+use std::fs;
+use std::process;
 
 fn main() {
-    let mut game = Game::default();
-    let mut renderer = GameRenderer::new(&mut game);
-    renderer.run();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = Command::parse(&args).unwrap_or_else(|err| {
+        exit_with_error(&format!("{}\n\n{}", err, USAGE));
+    });
+
+    match command {
+        Command::Play(options) => run_game(options),
+        Command::Perft { depth, fen } => run_perft(depth, &fen),
+        Command::PgnConvert { fen, moves } => run_pgn_convert(&fen, &moves),
+        Command::PgnReplay(path) => run_pgn_replay(&path),
+        Command::Fen(fen) => println!("{}", parse_board(&fen).to_fen()),
+        Command::Analyze(fen) => run_analyze(&fen),
+        Command::Help => println!("{}", USAGE),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn run_game(options: PlayOptions) {
+    let opponent = match options.opponent {
+        OpponentKind::Human => Opponent::Human,
+        OpponentKind::Random => Opponent::Random(RandomMover::from_time()),
+    };
+
+    let computer_name = match options.opponent {
+        OpponentKind::Human => None,
+        OpponentKind::Random => Some("Random mover".to_string()),
+    };
+    let (white, black) = match options.side {
+        Player::White => (options.white, options.black.or(computer_name)),
+        Player::Black => (options.white.or(computer_name), options.black),
+    };
+
+    let mut game = match &options.load {
+        Some((path, number)) => load_game(path, *number),
+        None => Game::from_board(parse_board(&options.fen)),
+    };
+    if let Some(time_control) = options.time_control {
+        game.start_clock(time_control);
+    }
+    if let Some(white) = white {
+        game.info.white = white;
+    }
+    if let Some(black) = black {
+        game.info.black = black;
+    }
+
+    let result = GameRenderer::new(&mut game, opponent, options.side, options.pgn_path.into())
+        .and_then(|mut renderer| renderer.run());
+
+    if let Err(err) = result {
+        exit_with_error(&err.to_string());
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+fn run_game(_options: cli::PlayOptions) {
+    exit_with_error("Built without the terminal client, enable the \"tui\" feature to play");
+}
+
+fn run_perft(depth: u32, fen: &str) {
+    let board = parse_board(fen);
+
+    let moves = divide(&board, depth);
+    for (a_move, nodes) in moves.iter() {
+        println!("{}: {}", a_move.to_uci(), nodes);
+    }
+
+    // Without any root move the count is the root itself
+    let total: u64 = match depth {
+        0 => 1,
+        _ => moves.iter().map(|(_, nodes)| nodes).sum(),
+    };
+
+    println!();
+    println!("Nodes searched: {}", total);
+}
+
+fn run_pgn_convert(fen: &str, moves: &[String]) {
+    let mut game = Game::from_board(parse_board(fen));
+
+    for (index, text) in moves.iter().enumerate() {
+        let result = game
+            .board
+            .parse_san(text)
+            .map_err(|err| err.to_string())
+            .and_then(|a_move| game.play_move(&a_move).map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            exit_with_error(&format!("Move {} ({}): {}", index + 1, text, err));
+        }
+    }
+
+    print!("{}", game.to_pgn());
+}
+
+fn run_pgn_replay(path: &str) {
+    for game in read_games(path) {
+        let result = match &game.result {
+            Some(result) => format!(
+                "{} by {}",
+                result.outcome.to_token(),
+                result.termination.to_label()
+            ),
+            None => "*".to_string(),
+        };
+        println!("{} - {}, {}", game.info.white, game.info.black, result);
+
+        let mut board = game.initial_board().clone();
+        for a_move in game.moves() {
+            let number = match board.turn {
+                Player::White => format!("{}.", board.fullmove_number),
+                Player::Black => format!("{}...", board.fullmove_number),
+            };
+            let san = board.to_san(a_move);
+
+            board.make_move(a_move);
+            println!("{:<6} {:<8} {}", number, san, board.to_fen());
+        }
+        println!();
+    }
+}
+
+fn read_games(path: &str) -> Vec<Game> {
+    let text = fs::read_to_string(path)
+        .unwrap_or_else(|err| exit_with_error(&format!("Could not read {}: {}", path, err)));
+
+    read_pgn(&text).unwrap_or_else(|err| exit_with_error(&format!("{}: {}", path, err)))
+}
+
+#[cfg(feature = "tui")]
+fn load_game(path: &str, number: usize) -> Game {
+    let mut games = read_games(path);
+
+    if number > games.len() {
+        exit_with_error(&format!(
+            "{} has {} games, there is no game {}",
+            path,
+            games.len(),
+            number
+        ));
+    }
+    games.swap_remove(number - 1)
+}
+
+fn run_analyze(fen: &str) {
+    let board = parse_board(fen);
+    let game = Game::from_board(board.clone());
+
+    let checkers: Vec<String> = board
+        .checkers()
+        .iter()
+        .map(|coordinate| coordinate.to_field_name())
+        .collect();
+    let moves: Vec<String> = board
+        .legal_moves()
+        .iter()
+        .map(|a_move| board.to_san(a_move))
+        .collect();
+
+    println!("FEN: {}", board.to_fen());
+    println!("Side to move: {}", board.turn.to_label());
+    match checkers.is_empty() {
+        true => println!("Check: no"),
+        false => println!("Check: yes, by {}", checkers.join(", ")),
+    }
+    match &game.result {
+        Some(result) => println!(
+            "Result: {} by {}",
+            result.outcome.to_token(),
+            result.termination.to_label()
+        ),
+        None => println!("Result: ongoing"),
+    }
+    println!("Legal moves ({}): {}", moves.len(), moves.join(" "));
+}
+
+fn parse_board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap_or_else(|err| exit_with_error(&err.to_string()))
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
 }